@@ -0,0 +1,216 @@
+use crate::{constant::*, health::Health, player::Player, state::GameState, weapon::Weapon};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+
+fn default_attack_range() -> f32 {
+    350.0
+}
+
+fn default_approach_time() -> f32 {
+    3.0
+}
+
+fn default_attack_time() -> f32 {
+    2.5
+}
+
+fn default_evade_time() -> f32 {
+    0.6
+}
+
+fn default_retreat_health() -> f32 {
+    0.3
+}
+
+fn default_duty_time() -> f32 {
+    15.0
+}
+
+// Tuning for a brain, as written in level data. Times are in seconds
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct BrainProfile {
+    // Distance to the player at which the ship stops closing in and opens fire
+    #[serde(default = "default_attack_range")]
+    pub attack_range: f32,
+    // Longest spent closing in before attacking from wherever it is
+    #[serde(default = "default_approach_time")]
+    pub approach_time: f32,
+    // Length of each burst before repositioning
+    #[serde(default = "default_attack_time")]
+    pub attack_time: f32,
+    // How long a hit sends it dodging
+    #[serde(default = "default_evade_time")]
+    pub evade_time: f32,
+    // Health fraction at which it gives up and leaves
+    #[serde(default = "default_retreat_health")]
+    pub retreat_health: f32,
+    // Time on screen before it leaves anyway
+    #[serde(default = "default_duty_time")]
+    pub duty_time: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrainState {
+    // Flying onto the screen, holding fire
+    Enter,
+    // Moving to a firing position above and to the side of the player
+    Approach,
+    // Strafing in place with the gun firing
+    Attack,
+    // Just took a hit, dodging sideways away from the player
+    Evade,
+    // Leaving off the top of the screen for good
+    Retreat,
+}
+
+#[derive(Component)]
+pub struct Brain {
+    pub profile: BrainProfile,
+    pub state: BrainState,
+    // Seconds spent in the current state
+    pub state_time: f32,
+    // Seconds since spawning
+    pub age: f32,
+    pub speed: f32,
+    // Sideways offset from the player this ship attacks from, so a group spreads out
+    pub flank: f32,
+    pub last_health: Option<u16>,
+}
+
+impl Brain {
+    pub fn new(profile: BrainProfile, speed: f32) -> Self {
+        Brain {
+            profile,
+            state: BrainState::Enter,
+            state_time: 0.0,
+            age: 0.0,
+            speed,
+            flank: rand::rng().random_range(-150.0..=150.0),
+            last_health: None,
+        }
+    }
+
+    fn switch(&mut self, state: BrainState) {
+        if self.state != state {
+            self.state = state;
+            self.state_time = 0.0;
+        }
+    }
+
+    // Pick the next state from timers, damage taken and distance to the player
+    fn transition(&mut self, position: Vec2, player: Option<Vec2>, health: &Health) {
+        let damaged = self.last_health.is_some_and(|last| health.current < last);
+        self.last_health = Some(health.current);
+        let profile = self.profile;
+
+        if self.state == BrainState::Retreat {
+            return;
+        }
+        if health.fraction() <= profile.retreat_health || self.age >= profile.duty_time {
+            self.switch(BrainState::Retreat);
+            return;
+        }
+        if damaged && self.state != BrainState::Enter {
+            self.switch(BrainState::Evade);
+            return;
+        }
+
+        let distance = player.map_or(f32::INFINITY, |target| target.distance(position));
+        match self.state {
+            BrainState::Enter => {
+                if position.y <= WINDOW_HEIGHT / 2.0 - ENEMY_SIZE.y * 2.0 {
+                    self.switch(BrainState::Approach);
+                }
+            }
+            BrainState::Approach => {
+                if distance <= profile.attack_range || self.state_time >= profile.approach_time {
+                    self.switch(BrainState::Attack);
+                }
+            }
+            BrainState::Attack => {
+                if self.state_time >= profile.attack_time || distance > profile.attack_range * 1.5 {
+                    self.switch(BrainState::Approach);
+                }
+            }
+            BrainState::Evade => {
+                if self.state_time >= profile.evade_time {
+                    self.switch(BrainState::Approach);
+                }
+            }
+            BrainState::Retreat => {}
+        }
+    }
+
+    // Velocity for the current state
+    fn velocity(&self, position: Vec2, player: Option<Vec2>) -> Vec2 {
+        match (self.state, player) {
+            (BrainState::Enter, _) => Vec2::new(0.0, -self.speed),
+            (BrainState::Retreat, _) => Vec2::new(0.0, self.speed),
+            (BrainState::Approach, Some(target)) => {
+                // Stand off above the player, easing in to arrive without overshooting
+                let standoff = Vec2::new(
+                    (target.x + self.flank).clamp(
+                        -WINDOW_WIDTH / 2.0 + ENEMY_SIZE.x,
+                        WINDOW_WIDTH / 2.0 - ENEMY_SIZE.x,
+                    ),
+                    (target.y + self.profile.attack_range * 0.8)
+                        .clamp(0.0, WINDOW_HEIGHT / 2.0 - ENEMY_SIZE.y * 2.0),
+                );
+                let to_standoff = standoff - position;
+                let arrive = (to_standoff.length() / 100.0).min(1.0);
+                to_standoff.normalize_or_zero() * self.speed * arrive
+            }
+            (BrainState::Attack, _) => {
+                let sway = (self.state_time * 2.0).cos();
+                Vec2::new(sway * self.speed * 0.3, 0.0)
+            }
+            (BrainState::Evade, Some(target)) => {
+                let side = if position.x >= target.x { 1.0 } else { -1.0 };
+                Vec2::new(side * self.speed * 1.5, self.speed * 0.5)
+            }
+            // Nobody to chase, wait where we are
+            (BrainState::Approach | BrainState::Evade, None) => Vec2::ZERO,
+        }
+    }
+}
+
+pub fn update_brains(
+    time: Res<Time>,
+    mut brain_query: Query<(
+        &mut Brain,
+        &Transform,
+        &Health,
+        &mut Velocity,
+        Option<&mut Weapon>,
+    )>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let player_position = player_query
+        .single()
+        .ok()
+        .map(|transform| transform.translation.truncate());
+
+    for (mut brain, transform, health, mut velocity, weapon) in brain_query.iter_mut() {
+        brain.age += time.delta_secs();
+        brain.state_time += time.delta_secs();
+
+        let position = transform.translation.truncate();
+        brain.transition(position, player_position, health);
+        velocity.linvel = brain.velocity(position, player_position);
+
+        // Only fire while attacking
+        if let Some(mut weapon) = weapon {
+            weapon.firing = brain.state == BrainState::Attack && player_position.is_some();
+        }
+    }
+}
+
+pub struct AiPlugin;
+
+impl Plugin for AiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_brains.run_if(in_state(GameState::Playing)));
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{
+    constant::*,
+    debris::Debris,
+    enemy::{Enemy, EnemyBullet},
+    health::DamageEvent,
+    player::Player,
+    state::GameState,
+};
+use bevy::{prelude::*, sprite::Anchor};
+use bevy_rapier2d::prelude::*;
+
+// Secondary fire: hold to charge, release to fire
+#[derive(Component, Default)]
+pub struct BeamCannon {
+    // 0.0 to 1.0
+    pub charge: f32,
+}
+
+// Glow at the ship's nose while charging
+#[derive(Component)]
+pub struct ChargeGlow;
+
+#[derive(Component)]
+pub struct Beam {
+    pub lifetime: Timer,
+    // Hits are checked on every tick rather than every frame
+    pub tick: Timer,
+    pub width: f32,
+}
+
+const CHARGE_FRAMES: usize = 7;
+
+#[allow(clippy::too_many_arguments)]
+pub fn charge_beam(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    mut player_query: Query<(Entity, &mut BeamCannon), With<Player>>,
+    mut glow_query: Query<(Entity, &mut Sprite), With<ChargeGlow>>,
+    beam_query: Query<(), With<Beam>>,
+) {
+    let Ok((player, mut cannon)) = player_query.single_mut() else {
+        return;
+    };
+
+    // Can't charge while a beam is still firing
+    if !beam_query.is_empty() {
+        return;
+    }
+
+    if keyboard_input.pressed(KeyCode::KeyX) || mouse_input.pressed(MouseButton::Right) {
+        let was_charged = cannon.charge >= 1.0;
+        cannon.charge = (cannon.charge + time.delta_secs() / BEAM_CHARGE_TIME).min(1.0);
+        if cannon.charge >= 1.0 && !was_charged {
+            commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_shieldUp.ogg")));
+        }
+
+        // Step through the beam part sprites as the charge builds
+        let frame = (cannon.charge * (CHARGE_FRAMES - 1) as f32) as usize;
+        let image = asset_server.load(format!("PNG/Parts/beam{}.png", frame));
+        let size = Vec2::splat(BEAM_WIDTH * (0.5 + cannon.charge));
+
+        if let Ok((_, mut sprite)) = glow_query.single_mut() {
+            sprite.image = image;
+            sprite.custom_size = Some(size);
+        } else {
+            commands.entity(player).with_children(|parent| {
+                parent.spawn((
+                    Transform::from_xyz(0.0, PLAYER_SIZE.y / 2.0, 0.1),
+                    Sprite {
+                        image,
+                        custom_size: Some(size),
+                        anchor: Anchor::Center,
+                        ..default()
+                    },
+                    ChargeGlow,
+                ));
+            });
+        }
+    } else if cannon.charge > 0.0 {
+        // Button released, fire whatever charge has built up
+        for (glow, _) in glow_query.iter() {
+            commands.entity(glow).despawn();
+        }
+
+        if cannon.charge >= BEAM_MIN_CHARGE {
+            // A fuller charge gives a wider and longer lasting beam
+            let width = BEAM_WIDTH * cannon.charge;
+            commands.entity(player).with_children(|parent| {
+                parent.spawn((
+                    Transform::from_xyz(0.0, PLAYER_SIZE.y / 2.0, 0.1),
+                    Sprite {
+                        image: asset_server.load("PNG/Parts/beamLong1.png"),
+                        custom_size: Some(Vec2::new(width, BEAM_LENGTH)),
+                        anchor: Anchor::BottomCenter,
+                        ..default()
+                    },
+                    Beam {
+                        lifetime: Timer::from_seconds(
+                            BEAM_DURATION * cannon.charge,
+                            TimerMode::Once,
+                        ),
+                        tick: Timer::from_seconds(BEAM_TICK, TimerMode::Repeating),
+                        width,
+                    },
+                ));
+            });
+            commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_laser2.ogg")));
+        }
+        cannon.charge = 0.0;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_beam(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    rapier_context: ReadRapierContext,
+    mut beam_query: Query<(Entity, &GlobalTransform, &mut Beam, &mut Sprite)>,
+    enemy_query: Query<(), With<Enemy>>,
+    enemy_bullet_query: Query<(), With<EnemyBullet>>,
+    debris_query: Query<(), With<Debris>>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let Ok(rapier_context) = rapier_context.single() else {
+        return;
+    };
+
+    for (entity, global_transform, mut beam, mut sprite) in beam_query.iter_mut() {
+        if beam.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        if !beam.tick.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        // Flicker between the two long beam sprites
+        let ticks = (beam.lifetime.elapsed_secs() / BEAM_TICK) as u32;
+        let frame = if ticks.is_multiple_of(2) {
+            "PNG/Parts/beamLong1.png"
+        } else {
+            "PNG/Parts/beamLong2.png"
+        };
+        sprite.image = asset_server.load(frame);
+
+        // Everything overlapping the beam's box is hit, not just the first collider
+        let origin = global_transform.translation().truncate();
+        let shape = Collider::cuboid(beam.width / 2.0, BEAM_LENGTH / 2.0);
+        let hits: Vec<Entity> = rapier_context.with_query_pipeline(
+            QueryFilter::default(),
+            |query_pipeline| {
+                query_pipeline
+                    .intersect_shape(
+                        origin + Vec2::new(0.0, BEAM_LENGTH / 2.0),
+                        0.0,
+                        &*shape.raw,
+                    )
+                    .collect()
+            },
+        );
+
+        for hit in hits {
+            // Rocks take damage too, so they split and score like any other hit
+            if enemy_query.contains(hit) || debris_query.contains(hit) {
+                damage_events.write(DamageEvent {
+                    target: hit,
+                    amount: BEAM_DAMAGE,
+                });
+            } else if enemy_bullet_query.contains(hit) {
+                commands.entity(hit).try_despawn();
+            }
+        }
+    }
+}
+
+pub struct BeamPlugin;
+
+impl Plugin for BeamPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (charge_beam, update_beam).run_if(in_state(GameState::Playing)),
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{
+    constant::*,
+    debris::{Debris, PlayerPushed, blast_impulse},
+    enemy::{Enemy, EnemyBullet},
+    health::DamageEvent,
+    player::{Invulnerable, Player},
+    state::{GameState, GameUI},
+};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::ExternalImpulse;
+
+// Full-screen white flash that fades out
+#[derive(Component)]
+pub struct BombFlash(pub Timer);
+
+// Expanding shockwave sprite centred on the player
+#[derive(Component)]
+pub struct BombBlast(pub Timer);
+
+#[allow(clippy::too_many_arguments)]
+pub fn use_bomb(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+    enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+    enemy_bullet_query: Query<Entity, With<EnemyBullet>>,
+    mut debris_query: Query<(Entity, &Transform, &mut ExternalImpulse), With<Debris>>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut game_values: ResMut<GameValues>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyB) || game_values.bombs == 0 {
+        return;
+    }
+    let Ok((player, player_transform)) = player_query.single() else {
+        return;
+    };
+    game_values.bombs -= 1;
+    let center = player_transform.translation.truncate();
+
+    // Every enemy bullet on screen is cleared
+    for bullet in enemy_bullet_query.iter() {
+        commands.entity(bullet).despawn();
+    }
+
+    // Enemies and debris only within the blast radius
+    for (enemy, transform) in enemy_query.iter() {
+        if transform.translation.truncate().distance(center) <= BOMB_RADIUS {
+            damage_events.write(DamageEvent {
+                target: enemy,
+                amount: BOMB_DAMAGE,
+            });
+        }
+    }
+    for (debris, transform, mut impulse) in debris_query.iter_mut() {
+        let position = transform.translation.truncate();
+        if position.distance(center) <= BOMB_RADIUS {
+            damage_events.write(DamageEvent {
+                target: debris,
+                amount: BOMB_DAMAGE,
+            });
+        } else if let Some(blast) = blast_impulse(center, position, BOMB_RADIUS * 1.5) {
+            // Rocks just outside the blast are flung away
+            *impulse += blast;
+            commands.entity(debris).insert(PlayerPushed::default());
+        }
+    }
+
+    commands
+        .entity(player)
+        .insert(Invulnerable(Timer::from_seconds(BOMB_INVULNERABILITY, TimerMode::Once)));
+
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.8)),
+        BombFlash(Timer::from_seconds(BOMB_FLASH_TIME, TimerMode::Once)),
+        GameUI,
+    ));
+    commands.spawn((
+        Transform::from_translation(center.extend(1.0)),
+        Sprite {
+            image: asset_server.load("PNG/Effects/star3.png"),
+            custom_size: Some(Vec2::splat(PLAYER_SIZE.x)),
+            ..default()
+        },
+        BombBlast(Timer::from_seconds(BOMB_FLASH_TIME, TimerMode::Once)),
+    ));
+    commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_shieldDown.ogg")));
+}
+
+pub fn update_bomb_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flash_query: Query<(Entity, &mut BombFlash, &mut BackgroundColor)>,
+    mut blast_query: Query<(Entity, &mut BombBlast, &mut Sprite)>,
+) {
+    for (entity, mut flash, mut background) in flash_query.iter_mut() {
+        if flash.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        } else {
+            background.0.set_alpha(0.8 * flash.0.fraction_remaining());
+        }
+    }
+
+    for (entity, mut blast, mut sprite) in blast_query.iter_mut() {
+        if blast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        } else {
+            // Grow out to the blast radius while fading
+            let size = PLAYER_SIZE.x + (BOMB_RADIUS * 2.0 - PLAYER_SIZE.x) * blast.0.fraction();
+            sprite.custom_size = Some(Vec2::splat(size));
+            sprite.color.set_alpha(blast.0.fraction_remaining());
+        }
+    }
+}
+
+pub struct BombPlugin;
+
+impl Plugin for BombPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (use_bomb, update_bomb_effects).run_if(in_state(GameState::Playing)),
+        );
+    }
+}
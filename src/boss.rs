@@ -0,0 +1,625 @@
+use crate::{
+    constant::*,
+    emitter::{Emitter, EmitterDef, EmitterPattern},
+    enemy::{Enemy, enemy_weapon},
+    health::{DamageEvent, Health, HitFlash},
+    outline::OutlineCollider,
+    pickup::{PickupKind, spawn_pickup},
+    state::{GameState, GameUI},
+    weapon::SpreadPattern,
+};
+use bevy::{color::palettes::css::WHITE, prelude::*};
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+use std::f32::consts::TAU;
+
+// End of level bosses, each a core hull surrounded by destructible parts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum BossKind {
+    // Black gunship with armoured wings and twin turrets
+    Dreadnought,
+    // Green flagship escorted by four blue and red gun pods
+    Mothership,
+}
+
+// A piece bolted onto the core, with its own collider and health
+pub struct BossPartDef {
+    pub sprite: &'static str,
+    pub size: Vec2,
+    // Position relative to the core
+    pub offset: Vec2,
+    pub flip_x: bool,
+    pub health: u16,
+    pub score: u32,
+    // Parts with a gun keep shooting at the player until destroyed
+    pub fire_interval: Option<f32>,
+}
+
+// Attack used while the core's health fraction is above `until`
+pub struct BossPhase {
+    pub until: f32,
+    pub emitters: Vec<EmitterDef>,
+    // Side to side sway in pixels
+    pub sway: f32,
+}
+
+pub struct BossDef {
+    pub name: &'static str,
+    pub sprite: &'static str,
+    pub size: Vec2,
+    pub health: u16,
+    pub score: u32,
+    pub speed: f32,
+    pub parts: Vec<BossPartDef>,
+    // Ordered from full health down
+    pub phases: Vec<BossPhase>,
+}
+
+impl BossKind {
+    pub fn definition(&self) -> BossDef {
+        match self {
+            BossKind::Dreadnought => BossDef {
+                name: "Dreadnought",
+                sprite: "PNG/Enemies/enemyBlack5.png",
+                size: Vec2::new(160.0, 140.0),
+                health: 120,
+                score: 2000,
+                speed: 80.0,
+                parts: vec![
+                    BossPartDef {
+                        sprite: "PNG/Parts/wingRed_3.png",
+                        size: Vec2::new(50.0, 96.0),
+                        offset: Vec2::new(-110.0, 10.0),
+                        flip_x: true,
+                        health: 30,
+                        score: 250,
+                        fire_interval: Some(1.6),
+                    },
+                    BossPartDef {
+                        sprite: "PNG/Parts/wingRed_3.png",
+                        size: Vec2::new(50.0, 96.0),
+                        offset: Vec2::new(110.0, 10.0),
+                        flip_x: false,
+                        health: 30,
+                        score: 250,
+                        fire_interval: Some(1.6),
+                    },
+                    BossPartDef {
+                        sprite: "PNG/Parts/turretBase_big.png",
+                        size: Vec2::new(41.0, 41.0),
+                        offset: Vec2::new(-45.0, -70.0),
+                        flip_x: false,
+                        health: 15,
+                        score: 150,
+                        fire_interval: Some(1.1),
+                    },
+                    BossPartDef {
+                        sprite: "PNG/Parts/turretBase_big.png",
+                        size: Vec2::new(41.0, 41.0),
+                        offset: Vec2::new(45.0, -70.0),
+                        flip_x: false,
+                        health: 15,
+                        score: 150,
+                        fire_interval: Some(1.1),
+                    },
+                ],
+                phases: vec![
+                    // Aimed triple shots
+                    BossPhase {
+                        until: 0.66,
+                        emitters: vec![EmitterDef::new(
+                            EmitterPattern::AimedFan {
+                                count: 3,
+                                angle: 12.0,
+                            },
+                            1.2,
+                        )],
+                        sway: 150.0,
+                    },
+                    // Walls to weave through, with fans to keep the player moving
+                    BossPhase {
+                        until: 0.33,
+                        emitters: vec![
+                            EmitterDef {
+                                speed: 150.0,
+                                ..EmitterDef::new(
+                                    EmitterPattern::Wall {
+                                        count: 14,
+                                        spacing: 60.0,
+                                        gap: 3,
+                                    },
+                                    2.0,
+                                )
+                            },
+                            EmitterDef::new(
+                                EmitterPattern::AimedFan {
+                                    count: 5,
+                                    angle: 15.0,
+                                },
+                                1.4,
+                            ),
+                        ],
+                        sway: 250.0,
+                    },
+                    // Spinning spiral over rings that hang, then accelerate
+                    BossPhase {
+                        until: 0.0,
+                        emitters: vec![
+                            EmitterDef::new(
+                                EmitterPattern::Spiral {
+                                    arms: 4,
+                                    turn_rate: 90.0,
+                                },
+                                0.15,
+                            ),
+                            EmitterDef {
+                                speed: 50.0,
+                                delay: 0.5,
+                                acceleration: 300.0,
+                                ..EmitterDef::new(EmitterPattern::Radial { count: 16 }, 1.5)
+                            },
+                        ],
+                        sway: 300.0,
+                    },
+                ],
+            },
+            BossKind::Mothership => BossDef {
+                name: "Mothership",
+                sprite: "PNG/Enemies/enemyGreen5.png",
+                size: Vec2::new(200.0, 173.0),
+                health: 160,
+                score: 3000,
+                speed: 60.0,
+                parts: [
+                    ("PNG/Enemies/enemyBlue4.png", -1.0, 1.0),
+                    ("PNG/Enemies/enemyRed4.png", 1.0, 1.0),
+                    ("PNG/Enemies/enemyRed4.png", -1.0, -1.0),
+                    ("PNG/Enemies/enemyBlue4.png", 1.0, -1.0),
+                ]
+                .into_iter()
+                .map(|(sprite, x, y)| BossPartDef {
+                    sprite,
+                    size: Vec2::new(56.0, 56.0),
+                    offset: Vec2::new(x * 110.0, y * 80.0),
+                    flip_x: false,
+                    health: 20,
+                    score: 200,
+                    fire_interval: Some(2.0),
+                })
+                .collect(),
+                phases: vec![
+                    // Aimed fans
+                    BossPhase {
+                        until: 0.5,
+                        emitters: vec![EmitterDef::new(
+                            EmitterPattern::AimedFan {
+                                count: 5,
+                                angle: 10.0,
+                            },
+                            1.5,
+                        )],
+                        sway: 200.0,
+                    },
+                    // Twin spirals turning in opposite directions
+                    BossPhase {
+                        until: 0.0,
+                        emitters: vec![EmitterDef::new(
+                            EmitterPattern::Combined(vec![
+                                EmitterPattern::Spiral {
+                                    arms: 3,
+                                    turn_rate: 120.0,
+                                },
+                                EmitterPattern::Spiral {
+                                    arms: 3,
+                                    turn_rate: -120.0,
+                                },
+                            ]),
+                            0.2,
+                        )],
+                        sway: 300.0,
+                    },
+                ],
+            },
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Boss {
+    pub kind: BossKind,
+    pub phase: usize,
+    // Seconds since reaching the hold line, None while still entering
+    pub holding: Option<f32>,
+}
+
+#[derive(Component)]
+pub struct BossPart {
+    pub boss: Entity,
+    pub offset: Vec2,
+    pub score: u32,
+}
+
+// Core is exploding, the boss is already beaten
+#[derive(Component)]
+pub struct BossDefeated {
+    pub timer: Timer,
+    pub explosions: Timer,
+}
+
+#[derive(Component)]
+pub struct BossExplosion(pub Timer);
+
+#[derive(Component)]
+pub struct BossHealthBar;
+
+#[derive(Component)]
+pub struct BossHealthFill;
+
+fn spawn_explosion(commands: &mut Commands, asset_server: &AssetServer, position: Vec2) {
+    commands.spawn((
+        Transform::from_translation(position.extend(1.0)),
+        Sprite {
+            image: asset_server.load("PNG/Effects/star2.png"),
+            custom_size: Some(Vec2::splat(20.0)),
+            ..default()
+        },
+        BossExplosion(Timer::from_seconds(0.4, TimerMode::Once)),
+    ));
+}
+
+// Spawn a boss above the screen with all of its parts, returns the core
+pub fn spawn_boss(commands: &mut Commands, asset_server: &AssetServer, kind: BossKind) -> Entity {
+    let def = kind.definition();
+    let position = Vec2::new(0.0, WINDOW_HEIGHT / 2.0 + def.size.y);
+    let velocity = Velocity {
+        linvel: Vec2::new(0.0, -def.speed),
+        angvel: 0.0,
+    };
+
+    let core = commands
+        .spawn((
+            RigidBody::Dynamic,
+            GravityScale(0.0),
+            velocity,
+            ActiveEvents::COLLISION_EVENTS,
+            Sensor,
+            Collider::cuboid(def.size.x / 2.0, def.size.y / 2.0),
+            OutlineCollider,
+            Transform::from_translation(position.extend(0.0)),
+            Sprite {
+                image: asset_server.load(def.sprite),
+                custom_size: Some(def.size),
+                ..default()
+            },
+            Enemy,
+            Boss {
+                kind,
+                phase: 0,
+                holding: None,
+            },
+            Health::new(def.health),
+            Emitter::new(def.phases[0].emitters.clone()),
+        ))
+        .id();
+
+    // Parts are separate bodies that track the core, so every system sees them in world space
+    for part in &def.parts {
+        let mut entity = commands.spawn((
+            RigidBody::Dynamic,
+            GravityScale(0.0),
+            velocity,
+            ActiveEvents::COLLISION_EVENTS,
+            Sensor,
+            Collider::cuboid(part.size.x / 2.0, part.size.y / 2.0),
+            OutlineCollider,
+            Transform::from_translation((position + part.offset).extend(0.5)),
+            Sprite {
+                image: asset_server.load(part.sprite),
+                custom_size: Some(part.size),
+                flip_x: part.flip_x,
+                ..default()
+            },
+            Enemy,
+            BossPart {
+                boss: core,
+                offset: part.offset,
+                score: part.score,
+            },
+            Health::new(part.health),
+        ));
+        if let Some(interval) = part.fire_interval {
+            let mut weapon = enemy_weapon(interval, SpreadPattern::SINGLE, BOSS_PART_LEAD);
+            weapon.muzzle = Vec2::new(0.0, -part.size.y / 2.0);
+            entity.insert(weapon);
+        }
+    }
+
+    commands.spawn(AudioPlayer::new(
+        asset_server.load("Bonus/sfx_shieldUp.ogg"),
+    ));
+    core
+}
+
+// Descend to the hold line, then sway side to side, faster in later phases
+pub fn move_boss(
+    time: Res<Time>,
+    mut boss_query: Query<(&mut Boss, &Transform, &mut Velocity), Without<BossDefeated>>,
+) {
+    for (mut boss, transform, mut velocity) in boss_query.iter_mut() {
+        let def = boss.kind.definition();
+        let sway = def.phases[boss.phase.min(def.phases.len() - 1)].sway;
+        let Some(held) = boss.holding.as_mut() else {
+            if transform.translation.y <= BOSS_HOLD_Y {
+                boss.holding = Some(0.0);
+            }
+            velocity.linvel = Vec2::new(0.0, -def.speed);
+            continue;
+        };
+
+        *held += time.delta_secs();
+        let w = TAU * BOSS_SWAY_FREQUENCY;
+        velocity.linvel = Vec2::new(sway * w * (w * *held).cos(), 0.0);
+    }
+}
+
+pub fn sync_boss_parts(
+    mut commands: Commands,
+    time: Res<Time>,
+    boss_query: Query<&Transform, (With<Boss>, Without<BossPart>)>,
+    mut part_query: Query<(Entity, &BossPart, &Transform, &mut Velocity)>,
+) {
+    let delta = time.delta_secs();
+    if delta <= 0.0 {
+        return;
+    }
+
+    for (entity, part, transform, mut velocity) in part_query.iter_mut() {
+        let Ok(core) = boss_query.get(part.boss) else {
+            commands.entity(entity).try_despawn();
+            continue;
+        };
+        // Steer exactly onto the mount point, like path followers do
+        let target = core.translation.truncate() + part.offset;
+        velocity.linvel = (target - transform.translation.truncate()) / delta;
+    }
+}
+
+// Switch attack pattern as the core loses health
+pub fn update_boss_phase(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut boss_query: Query<(Entity, &mut Boss, &Health), Without<BossDefeated>>,
+) {
+    for (entity, mut boss, health) in boss_query.iter_mut() {
+        let def = boss.kind.definition();
+        let phase = def
+            .phases
+            .iter()
+            .position(|phase| health.fraction() > phase.until)
+            .unwrap_or(def.phases.len() - 1);
+        if phase != boss.phase {
+            boss.phase = phase;
+            commands
+                .entity(entity)
+                .insert(Emitter::new(def.phases[phase].emitters.clone()));
+            commands.spawn(AudioPlayer::new(
+                asset_server.load("Bonus/sfx_shieldDown.ogg"),
+            ));
+        }
+    }
+}
+
+// Boss counterpart to `apply_enemy_damage`, for cores and their parts
+#[allow(clippy::type_complexity)]
+pub fn apply_boss_damage(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut damage_events: EventReader<DamageEvent>,
+    mut boss_query: Query<(&mut Health, &mut Velocity), (With<Boss>, Without<BossDefeated>)>,
+    mut part_query: Query<(&mut Health, &BossPart, &Transform), Without<Boss>>,
+    mut game_values: ResMut<GameValues>,
+) {
+    for event in damage_events.read() {
+        if let Ok((mut health, part, transform)) = part_query.get_mut(event.target) {
+            if health.current == 0 {
+                continue;
+            }
+            if health.take(event.amount) {
+                game_values.score += part.score;
+                spawn_explosion(
+                    &mut commands,
+                    &asset_server,
+                    transform.translation.truncate(),
+                );
+                commands.entity(event.target).try_despawn();
+                commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_zap.ogg")));
+            } else {
+                commands.entity(event.target).insert(HitFlash::default());
+            }
+            continue;
+        }
+
+        let Ok((mut health, mut velocity)) = boss_query.get_mut(event.target) else {
+            continue;
+        };
+        if health.current == 0 {
+            continue;
+        }
+        if health.take(event.amount) {
+            // Stop fighting back and start the defeat sequence
+            velocity.linvel = Vec2::ZERO;
+            commands
+                .entity(event.target)
+                .remove::<(Enemy, Emitter, Collider)>()
+                .insert(BossDefeated {
+                    timer: Timer::from_seconds(BOSS_DEFEAT_TIME, TimerMode::Once),
+                    explosions: Timer::from_seconds(0.15, TimerMode::Repeating),
+                });
+        } else {
+            commands.entity(event.target).insert(HitFlash::default());
+        }
+    }
+}
+
+// Chain of explosions over the wreck, then the score and a reward drop
+pub fn run_boss_defeat(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut boss_query: Query<(Entity, &Boss, &mut BossDefeated, &Transform, &mut Sprite)>,
+    part_query: Query<(Entity, &BossPart, &Transform)>,
+    mut game_values: ResMut<GameValues>,
+) {
+    let mut rng = rand::rng();
+    for (entity, boss, mut defeated, transform, mut sprite) in boss_query.iter_mut() {
+        let def = boss.kind.definition();
+        let center = transform.translation.truncate();
+
+        // Remaining parts go up with the first blast
+        for (part_entity, part, part_transform) in part_query.iter() {
+            if part.boss == entity {
+                spawn_explosion(
+                    &mut commands,
+                    &asset_server,
+                    part_transform.translation.truncate(),
+                );
+                commands.entity(part_entity).try_despawn();
+            }
+        }
+
+        if defeated.explosions.tick(time.delta()).just_finished() {
+            let offset = Vec2::new(
+                rng.random_range(-0.5..0.5) * def.size.x,
+                rng.random_range(-0.5..0.5) * def.size.y,
+            );
+            spawn_explosion(&mut commands, &asset_server, center + offset);
+            commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_zap.ogg")));
+        }
+
+        sprite.color = Color::WHITE.with_alpha(defeated.timer.fraction_remaining());
+        if defeated.timer.tick(time.delta()).finished() {
+            game_values.score += def.score;
+            spawn_pickup(
+                &mut commands,
+                &asset_server,
+                PickupKind::WeaponUpgrade,
+                center,
+            );
+            spawn_pickup(
+                &mut commands,
+                &asset_server,
+                PickupKind::Bomb,
+                center + Vec2::new(60.0, 0.0),
+            );
+            commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_twoTone.ogg")));
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub fn update_boss_explosions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut explosion_query: Query<(Entity, &mut BossExplosion, &mut Sprite)>,
+) {
+    for (entity, mut explosion, mut sprite) in explosion_query.iter_mut() {
+        if explosion.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        } else {
+            sprite.custom_size = Some(Vec2::splat(20.0 + 80.0 * explosion.0.fraction()));
+            sprite.color.set_alpha(explosion.0.fraction_remaining());
+        }
+    }
+}
+
+// Shows the core's health at the top of the screen while a boss is alive
+pub fn update_boss_health_bar(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    boss_query: Query<(&Boss, &Health)>,
+    bar_query: Query<Entity, With<BossHealthBar>>,
+    mut fill_query: Query<&mut Node, With<BossHealthFill>>,
+) {
+    let Some((boss, health)) = boss_query.iter().next() else {
+        for bar in bar_query.iter() {
+            commands.entity(bar).despawn();
+        }
+        return;
+    };
+
+    if bar_query.is_empty() {
+        commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(25.0),
+                    top: Val::Px(55.0),
+                    width: Val::Percent(50.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+                BossHealthBar,
+                // Rebuilt with the rest of the HUD when returning from pause
+                GameUI,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new(boss.kind.definition().name),
+                    TextFont {
+                        font: asset_server.load("Bonus/kenvector_future.ttf"),
+                        font_size: 18.0,
+                        ..Default::default()
+                    },
+                    TextColor(WHITE.into()),
+                ));
+                parent
+                    .spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Px(14.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.2)),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Node {
+                                width: Val::Percent(100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.85, 0.15, 0.15)),
+                            BossHealthFill,
+                        ));
+                    });
+            });
+        return;
+    }
+
+    for mut node in fill_query.iter_mut() {
+        node.width = Val::Percent(health.fraction() * 100.0);
+    }
+}
+
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                move_boss,
+                sync_boss_parts,
+                update_boss_phase,
+                apply_boss_damage,
+                run_boss_defeat,
+                update_boss_explosions,
+                update_boss_health_bar,
+            )
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
@@ -1,20 +1,35 @@
 use crate::{
-    constant::GameValues,
-    debris::Debris,
-    enemy::{Enemy, EnemyBullet},
-    player::{Player, PlayerBullet},
+    boss::{Boss, BossPart},
+    constant::{
+        DEBRIS_BULLET_IMPULSE, DEBRIS_INVULNERABILITY, DEBRIS_KNOCKBACK, GameValues,
+        MISSILE_PICKUP_AMMO,
+    },
+    debris::{Debris, PlayerPushed},
+    enemy::{Enemy, EnemyBullet, EnvironmentalHit, NeutralHit},
+    health::DamageEvent,
+    kamikaze::{Detonate, Kamikaze},
+    missile::Missile,
+    pickup::{Pickup, PickupKind},
+    player::{Invulnerable, Knockback, MAX_WEAPON_LEVEL, Player, PlayerBullet, PlayerMotion},
     state::GameState,
+    weapon::Damage,
 };
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 use bevy_rapier2d::prelude::*;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum EntityType {
     Player,
     Enemy,
+    // Boss cores and parts, which survive ramming
+    Boss,
+    // Blows up on contact instead of just ramming
+    Kamikaze,
     PlayerBullet,
     EnemyBullet,
+    Missile,
     Debris,
+    Pickup(PickupKind),
     Unknown,
 }
 
@@ -64,24 +79,48 @@ pub fn display_events(
     }
 }
 
-fn get_entity_type(
-    entity: Entity,
-    player_query: &Query<Entity, With<Player>>,
-    enemy_query: &Query<Entity, With<Enemy>>,
-    player_bullet_query: &Query<Entity, With<PlayerBullet>>,
-    enemy_bullet_query: &Query<Entity, With<EnemyBullet>>,
-    debris_query: &Query<Entity, With<Debris>>,
-) -> EntityType {
-    if player_query.contains(entity) {
+// Lookups used to classify and resolve both sides of a collision
+#[derive(SystemParam)]
+#[allow(clippy::type_complexity)]
+pub struct CollisionQueries<'w, 's> {
+    player: Query<'w, 's, Entity, With<Player>>,
+    enemy: Query<'w, 's, Entity, With<Enemy>>,
+    boss: Query<'w, 's, (), Or<(With<Boss>, With<BossPart>)>>,
+    kamikaze: Query<'w, 's, (), With<Kamikaze>>,
+    player_bullet: Query<'w, 's, Entity, With<PlayerBullet>>,
+    enemy_bullet: Query<'w, 's, Entity, With<EnemyBullet>>,
+    missile: Query<'w, 's, Entity, With<Missile>>,
+    debris: Query<'w, 's, Entity, With<Debris>>,
+    debris_body: Query<'w, 's, (&'static Debris, &'static Transform, &'static Velocity)>,
+    debris_impulse: Query<'w, 's, &'static mut ExternalImpulse, With<Debris>>,
+    enemy_body: Query<'w, 's, &'static Velocity, With<Enemy>>,
+    pushed: Query<'w, 's, (), With<PlayerPushed>>,
+    bullet_body: Query<'w, 's, (&'static Transform, &'static Velocity), With<PlayerBullet>>,
+    player_body: Query<'w, 's, (&'static Transform, &'static PlayerMotion), With<Player>>,
+    pickup: Query<'w, 's, &'static Pickup>,
+    damage: Query<'w, 's, &'static Damage>,
+    invulnerable: Query<'w, 's, (), With<Invulnerable>>,
+}
+
+fn get_entity_type(entity: Entity, queries: &CollisionQueries) -> EntityType {
+    if queries.player.contains(entity) {
         EntityType::Player
-    } else if enemy_query.contains(entity) {
+    } else if queries.boss.contains(entity) {
+        EntityType::Boss
+    } else if queries.kamikaze.contains(entity) {
+        EntityType::Kamikaze
+    } else if queries.enemy.contains(entity) {
         EntityType::Enemy
-    } else if player_bullet_query.contains(entity) {
+    } else if queries.player_bullet.contains(entity) {
         EntityType::PlayerBullet
-    } else if enemy_bullet_query.contains(entity) {
+    } else if queries.enemy_bullet.contains(entity) {
         EntityType::EnemyBullet
-    } else if debris_query.contains(entity) {
+    } else if queries.missile.contains(entity) {
+        EntityType::Missile
+    } else if queries.debris.contains(entity) {
         EntityType::Debris
+    } else if let Ok(pickup) = queries.pickup.get(entity) {
+        EntityType::Pickup(pickup.0)
     } else {
         EntityType::Unknown
     }
@@ -96,7 +135,9 @@ fn handle_collision_and_despawn(
     asset_server: &ResMut<AssetServer>,
     despawned_entities: &mut std::collections::HashSet<Entity>,
     game_values: &mut ResMut<GameValues>,
-    mut next_state: &mut ResMut<NextState<GameState>>,
+    next_state: &mut ResMut<NextState<GameState>>,
+    queries: &mut CollisionQueries,
+    damage_events: &mut EventWriter<DamageEvent>,
 ) -> bool {
     use EntityType::*;
 
@@ -109,15 +150,117 @@ fn handle_collision_and_despawn(
                 (entity2, entity1)
             };
             println!("Player collided with enemy!");
+            if !queries.invulnerable.contains(player) {
+                handle_player_damage(
+                    commands,
+                    asset_server,
+                    player,
+                    1,
+                    despawned_entities,
+                    game_values,
+                    next_state,
+                );
+            }
+            despawn_entity(commands, enemy, despawned_entities);
+            true
+        }
+
+        // Player vs Boss (either order), the boss shrugs it off
+        (Player, Boss) | (Boss, Player) => {
+            let player = if type1 == Player { entity1 } else { entity2 };
+            println!("Player rammed the boss!");
+            if !queries.invulnerable.contains(player) {
+                handle_player_damage(
+                    commands,
+                    asset_server,
+                    player,
+                    1,
+                    despawned_entities,
+                    game_values,
+                    next_state,
+                );
+            }
+            true
+        }
+
+        // Player vs Kamikaze (either order), the blast does the damage
+        (Player, Kamikaze) | (Kamikaze, Player) => {
+            let kamikaze = if type1 == Kamikaze { entity1 } else { entity2 };
+            println!("Kamikaze reached the player!");
+            commands.entity(kamikaze).try_insert(Detonate);
+            true
+        }
+
+        // Player vs Debris (either order), harder and faster rocks hit harder
+        (Player, Debris) | (Debris, Player) => {
+            let (player, debris) = if type1 == Player {
+                (entity1, entity2)
+            } else {
+                (entity2, entity1)
+            };
+            if queries.invulnerable.contains(player) {
+                return true;
+            }
+            let (Ok((rock, rock_transform, rock_velocity)), Ok((player_transform, motion))) = (
+                queries.debris_body.get(debris),
+                queries.player_body.get(player),
+            ) else {
+                return false;
+            };
+            println!("Player hit by debris!");
+
+            let closing_speed = (rock_velocity.linvel - motion.0).length();
+            let mass = rock.size.mass();
+            let damage = rock.size.impact_damage(closing_speed);
             handle_player_damage(
                 commands,
                 asset_server,
                 player,
+                damage.min(u8::MAX as u16) as u8,
                 despawned_entities,
                 game_values,
-                &mut next_state,
+                next_state,
             );
-            despawn_entity(commands, enemy, despawned_entities);
+
+            // Shove the ship away from the rock's centre
+            let away = (player_transform.translation - rock_transform.translation)
+                .truncate()
+                .normalize_or(Vec2::NEG_Y);
+            let shove = (closing_speed * mass).clamp(DEBRIS_KNOCKBACK.0, DEBRIS_KNOCKBACK.1);
+            commands.entity(player).try_insert((
+                Knockback(away * shove),
+                Invulnerable(Timer::from_seconds(DEBRIS_INVULNERABILITY, TimerMode::Once)),
+            ));
+            true
+        }
+
+        // Enemy vs Debris (either order), bosses are too big to notice
+        (Enemy | Kamikaze, Debris) | (Debris, Enemy | Kamikaze) => {
+            let (enemy, debris) = if type2 == Debris {
+                (entity1, entity2)
+            } else {
+                (entity2, entity1)
+            };
+            let (Ok((rock, _, rock_velocity)), Ok(enemy_velocity)) = (
+                queries.debris_body.get(debris),
+                queries.enemy_body.get(enemy),
+            ) else {
+                return false;
+            };
+            println!("Enemy hit by debris!");
+
+            // A rock the player sent flying earns them the kill, a stray one earns nothing
+            if queries.pushed.contains(debris) {
+                commands.entity(enemy).try_insert(EnvironmentalHit);
+            } else {
+                commands.entity(enemy).try_insert(NeutralHit);
+            }
+            damage_events.write(DamageEvent {
+                target: enemy,
+                amount: rock
+                    .size
+                    .impact_damage((rock_velocity.linvel - enemy_velocity.linvel).length()),
+            });
             true
         }
 
@@ -129,45 +272,110 @@ fn handle_collision_and_despawn(
                 (entity2, entity1)
             };
             println!("Player hit by enemy bullet!");
-            handle_player_damage(
-                commands,
-                asset_server,
-                player,
-                despawned_entities,
-                game_values,
-                &mut next_state,
-            );
+            let damage = queries.damage.get(bullet).map_or(1, |damage| damage.0);
+            if !queries.invulnerable.contains(player) {
+                handle_player_damage(
+                    commands,
+                    asset_server,
+                    player,
+                    damage,
+                    despawned_entities,
+                    game_values,
+                    next_state,
+                );
+            }
             despawn_entity(commands, bullet, despawned_entities);
             true
         }
 
-        // Any Bullet vs Debris (either order)
-        (PlayerBullet, Debris)
-        | (Debris, PlayerBullet)
-        | (EnemyBullet, Debris)
-        | (Debris, EnemyBullet) => {
-            let bullet = if matches!(type1, PlayerBullet | EnemyBullet) {
+        // Player Bullet vs Debris (either order), chips away at the rock and knocks it spinning
+        (PlayerBullet, Debris) | (Debris, PlayerBullet) => {
+            let (bullet, debris) = if type1 == PlayerBullet {
+                (entity1, entity2)
+            } else {
+                (entity2, entity1)
+            };
+            println!("Player bullet hit debris!");
+            if let (Ok((bullet_transform, bullet_velocity)), Ok((_, rock_transform, _))) = (
+                queries.bullet_body.get(bullet),
+                queries.debris_body.get(debris),
+            ) {
+                let hit = ExternalImpulse::at_point(
+                    bullet_velocity.linvel.normalize_or_zero() * DEBRIS_BULLET_IMPULSE,
+                    bullet_transform.translation.truncate(),
+                    rock_transform.translation.truncate(),
+                );
+                if let Ok(mut impulse) = queries.debris_impulse.get_mut(debris) {
+                    *impulse += hit;
+                }
+                commands.entity(debris).try_insert(PlayerPushed::default());
+            }
+            damage_events.write(DamageEvent {
+                target: debris,
+                amount: queries
+                    .damage
+                    .get(bullet)
+                    .map_or(1, |damage| damage.0 as u16),
+            });
+            despawn_entity(commands, bullet, despawned_entities);
+            true
+        }
+
+        // Enemy Bullet vs Debris (either order), rocks soak up enemy fire
+        (EnemyBullet, Debris) | (Debris, EnemyBullet) => {
+            let bullet = if type1 == EnemyBullet {
                 entity1
             } else {
                 entity2
             };
-            println!("Bullet hit debris!");
+            println!("Enemy bullet hit debris!");
             despawn_entity(commands, bullet, despawned_entities);
             true
         }
 
         // Player Bullet vs Enemy (either order)
-        (PlayerBullet, Enemy) | (Enemy, PlayerBullet) => {
+        (PlayerBullet, Enemy | Boss | Kamikaze) | (Enemy | Boss | Kamikaze, PlayerBullet) => {
             let (bullet, enemy) = if type1 == PlayerBullet {
                 (entity1, entity2)
             } else {
                 (entity2, entity1)
             };
             println!("Player bullet hit enemy!");
-            despawn_entity(commands, enemy, despawned_entities);
+            damage_events.write(DamageEvent {
+                target: enemy,
+                amount: queries
+                    .damage
+                    .get(bullet)
+                    .map_or(1, |damage| damage.0 as u16),
+            });
             despawn_entity(commands, bullet, despawned_entities);
-            commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_zap.ogg")));
-            game_values.score += 5;
+            true
+        }
+
+        // Missile vs Enemy (either order)
+        (Missile, Enemy | Boss | Kamikaze) | (Enemy | Boss | Kamikaze, Missile) => {
+            let (missile, enemy) = if type1 == Missile {
+                (entity1, entity2)
+            } else {
+                (entity2, entity1)
+            };
+            println!("Missile hit enemy!");
+            damage_events.write(DamageEvent {
+                target: enemy,
+                amount: queries
+                    .damage
+                    .get(missile)
+                    .map_or(1, |damage| damage.0 as u16),
+            });
+            despawn_entity(commands, missile, despawned_entities);
+            true
+        }
+
+        // Enemy Bullet vs Missile (either order), missiles can be shot down
+        (Missile, EnemyBullet) | (EnemyBullet, Missile) => {
+            println!("Missile shot down!");
+            despawn_entity(commands, entity1, despawned_entities);
+            despawn_entity(commands, entity2, despawned_entities);
             true
         }
 
@@ -179,6 +387,26 @@ fn handle_collision_and_despawn(
             true
         }
 
+        // Player vs Pickup (either order)
+        (Player, Pickup(kind)) | (Pickup(kind), Player) => {
+            let pickup = if type1 == Player { entity2 } else { entity1 };
+            println!("Player collected {:?}!", kind);
+            match kind {
+                PickupKind::WeaponUpgrade => {
+                    game_values.weapon_level = (game_values.weapon_level + 1).min(MAX_WEAPON_LEVEL);
+                }
+                PickupKind::MissileAmmo => {
+                    game_values.missiles = game_values.missiles.saturating_add(MISSILE_PICKUP_AMMO);
+                }
+                PickupKind::Bomb => {
+                    game_values.bombs = game_values.bombs.saturating_add(1);
+                }
+            }
+            commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_twoTone.ogg")));
+            despawn_entity(commands, pickup, despawned_entities);
+            true
+        }
+
         // No collision handling needed
         _ => false,
     }
@@ -188,19 +416,21 @@ fn handle_player_damage(
     commands: &mut Commands,
     asset_server: &ResMut<AssetServer>,
     player: Entity,
+    damage: u8,
     despawned_entities: &mut std::collections::HashSet<Entity>,
     game_values: &mut ResMut<GameValues>,
     next_state: &mut ResMut<NextState<GameState>>,
 ) {
-    if game_values.health <= 1 {
+    if game_values.health <= damage {
         game_values.health = 0;
+        game_values.weapon_level = 0;
         commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_lose.ogg")));
         println!("Player died! Setting state to GameOver");
         next_state.set(GameState::GameOver);
         despawn_entity(commands, player, despawned_entities);
         println!("State change queued, player despawned");
     } else {
-        game_values.health -= 1;
+        game_values.health -= damage;
         commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_zap.ogg")));
     }
 }
@@ -220,11 +450,8 @@ pub fn handle_collisions(
     mut commands: Commands,
     asset_server: ResMut<AssetServer>,
     mut collision_events: EventReader<CollisionEvent>,
-    player_query: Query<Entity, With<Player>>,
-    debris_query: Query<Entity, With<Debris>>,
-    enemy_query: Query<Entity, With<Enemy>>,
-    player_bullet_query: Query<Entity, With<PlayerBullet>>,
-    enemy_bullet_query: Query<Entity, With<EnemyBullet>>,
+    mut queries: CollisionQueries,
+    mut damage_events: EventWriter<DamageEvent>,
     mut game_values: ResMut<GameValues>,
     mut next_state: ResMut<NextState<GameState>>,
     current_state: Res<State<GameState>>,
@@ -242,22 +469,8 @@ pub fn handle_collisions(
                 continue;
             }
 
-            let type1 = get_entity_type(
-                *entity1,
-                &player_query,
-                &enemy_query,
-                &player_bullet_query,
-                &enemy_bullet_query,
-                &debris_query,
-            );
-            let type2 = get_entity_type(
-                *entity2,
-                &player_query,
-                &enemy_query,
-                &player_bullet_query,
-                &enemy_bullet_query,
-                &debris_query,
-            );
+            let type1 = get_entity_type(*entity1, &queries);
+            let type2 = get_entity_type(*entity2, &queries);
 
             handle_collision_and_despawn(
                 *entity1,
@@ -269,6 +482,8 @@ pub fn handle_collisions(
                 &mut despawned_entities,
                 &mut game_values,
                 &mut next_state,
+                &mut queries,
+                &mut damage_events,
             );
         }
     }
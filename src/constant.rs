@@ -8,9 +8,66 @@ pub const PLAYER_SPEED: f32 = 250.0; // pixels per second
 pub const BULLET_SPEED: f32 = 500.0;
 pub const PLAYER_SIZE: Vec2 = Vec2::new(40.0, 40.0);
 pub const BULLET_SIZE: Vec2 = Vec2::new(4.0, 15.0);
-pub const MAX_ENEMY: u8 = 4;
-pub const MIN_ENEMY: u8 = 1;
 pub const ENEMY_SIZE: Vec2 = Vec2::new(40.0, 40.0);
+pub const ENEMY_HOLD_Y: f32 = WINDOW_HEIGHT / 4.0; // where strafing and hovering enemies stop
+pub const PICKUP_SIZE: Vec2 = Vec2::new(30.0, 30.0);
+pub const PICKUP_SPEED: f32 = 100.0;
+pub const BEAM_CHARGE_TIME: f32 = 1.5; // seconds to reach full charge
+pub const BEAM_MIN_CHARGE: f32 = 0.25;
+pub const BEAM_DURATION: f32 = 2.0; // seconds at full charge
+pub const BEAM_WIDTH: f32 = 24.0;
+pub const BEAM_LENGTH: f32 = WINDOW_HEIGHT;
+pub const BEAM_TICK: f32 = 0.1;
+pub const BEAM_DAMAGE: u16 = 1; // per tick
+pub const MISSILE_SIZE: Vec2 = Vec2::new(10.0, 24.0);
+pub const MISSILE_SPEED: f32 = 400.0;
+pub const MISSILE_TURN_RATE: f32 = 4.0; // radians per second
+pub const MISSILE_LIFETIME: f32 = 4.0;
+pub const MISSILE_AMMO: u8 = 5;
+pub const MISSILE_DAMAGE: u8 = 6;
+pub const MISSILE_PICKUP_AMMO: u8 = 3;
+pub const BOMB_STOCK: u8 = 2;
+pub const BOMB_RADIUS: f32 = 400.0;
+pub const BOMB_INVULNERABILITY: f32 = 1.5;
+pub const BOMB_FLASH_TIME: f32 = 0.5;
+pub const BOMB_DAMAGE: u16 = 10;
+pub const BOSS_HOLD_Y: f32 = WINDOW_HEIGHT / 4.0;
+pub const BOSS_SWAY_FREQUENCY: f32 = 0.15;
+pub const BOSS_DEFEAT_TIME: f32 = 2.5; // seconds of explosions before the boss is gone
+pub const BOSS_PART_LEAD: f32 = 0.6;
+pub const SQUAD_SWAY_FREQUENCY: f32 = 0.1;
+pub const SQUAD_DIVE_SPEEDUP: f32 = 1.5;
+pub const SQUAD_DIVE_TURN_RATE: f32 = 3.0; // radians per second
+pub const SQUAD_DIVE_TIME: f32 = 4.0; // longest a dive lasts before pulling up
+pub const KAMIKAZE_ARM_TIME: f32 = 1.2; // warning before locking on
+pub const KAMIKAZE_FUSE: f32 = 4.0; // seconds of homing before it blows up anyway
+pub const KAMIKAZE_ACCELERATION: f32 = 350.0;
+pub const KAMIKAZE_MAX_SPEED: f32 = 420.0;
+pub const KAMIKAZE_TURN_RATE: f32 = 2.0; // radians per second
+pub const KAMIKAZE_BLAST_RADIUS: f32 = 90.0;
+pub const KAMIKAZE_DAMAGE: u16 = 2;
+pub const TERRAIN_SCROLL_SPEED: f32 = 60.0; // ground turrets drift down at this speed
+pub const TURRET_TURN_RATE: f32 = 1.5; // radians per second
+pub const TURRET_FIRE_ARC: f32 = 0.2; // radians off target a turret will still shoot
+pub const STEERING_SEPARATION_RADIUS: f32 = ENEMY_SIZE.x * 1.5;
+pub const STEERING_AVOID_MARGIN: f32 = 40.0; // clearance kept around debris
+pub const STEERING_MAX_SPEED: f32 = 150.0; // strongest sideways push steering can add
+pub const DEBRIS_IMPACT_SPEED: f32 = 150.0; // closing speed at which a rock deals its mass in damage
+pub const DEBRIS_KNOCKBACK: (f32, f32) = (200.0, 600.0); // min and max shove from an impact
+pub const DEBRIS_INVULNERABILITY: f32 = 1.0;
+pub const DEBRIS_RESTITUTION: f32 = 0.8;
+pub const DEBRIS_BULLET_IMPULSE: f32 = 50.0; // push from a single player bullet
+pub const DEBRIS_BLAST_IMPULSE: f32 = 300.0; // push at the centre of an explosion
+pub const ENVIRONMENTAL_KILL_WINDOW: f32 = 3.0; // seconds a shot rock still credits the player
+pub const ENVIRONMENTAL_KILL_BONUS: u32 = 100;
+pub const KNOCKBACK_DAMPING: f32 = 6.0; // how fast a shove dies down, per second
+pub const UFO_SIZE: Vec2 = Vec2::new(64.0, 64.0);
+pub const UFO_SPEED: f32 = 180.0;
+pub const UFO_HEALTH: u16 = 3;
+pub const UFO_INTERVAL: (f32, f32) = (25.0, 45.0); // seconds between saucers, picked at random
+pub const UFO_HUM_PITCH: f32 = 110.0; // Hz, base tone of the saucer's hum
+pub const UFO_HUM_WARBLE: (f32, f32) = (35.0, 6.0); // pitch swing in Hz and how many times a second
+pub const UFO_SCORES: [u32; 4] = [100, 150, 300, 500]; // mystery bonus, rolled on the kill
 
 #[derive(Resource, Default)]
 pub struct GameAssets {
@@ -24,6 +81,9 @@ pub struct Bullet;
 
 #[derive(Resource)]
 pub struct GameValues {
-    pub score: u16,
+    pub score: u32,
     pub health: u8,
+    pub weapon_level: u8,
+    pub missiles: u8,
+    pub bombs: u8,
 }
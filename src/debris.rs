@@ -1,13 +1,421 @@
-use crate::constant::*;
+use crate::{
+    constant::*,
+    health::{DamageEvent, HIT_FLASH_COLOR, Health, HitFlash},
+    outline::OutlineCollider,
+    state::GameState,
+};
 use bevy::{asset::LoadedFolder, prelude::*};
 use bevy_rapier2d::prelude::*;
-use rand::Rng;
+use rand::{Rng, seq::IndexedRandom};
+use serde::Deserialize;
 
 #[derive(Resource)]
 pub struct DebrisTimer(pub Timer);
 
+// Meteor sizes, each breaking into a few of the next size down when destroyed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MeteorSize {
+    Big,
+    Medium,
+    Small,
+    Tiny,
+}
+
+impl MeteorSize {
+    // Tag used in the `PNG/Meteors` file names
+    pub fn name(&self) -> &'static str {
+        match self {
+            MeteorSize::Big => "big",
+            MeteorSize::Medium => "med",
+            MeteorSize::Small => "small",
+            MeteorSize::Tiny => "tiny",
+        }
+    }
+
+    pub fn radius(&self) -> f32 {
+        match self {
+            MeteorSize::Big => 42.0,
+            MeteorSize::Medium => 20.0,
+            MeteorSize::Small => 13.0,
+            MeteorSize::Tiny => 8.0,
+        }
+    }
+
+    // How hard it hits and how hard it is to push around
+    pub fn mass(&self) -> f32 {
+        match self {
+            MeteorSize::Big => 3.0,
+            MeteorSize::Medium => 1.5,
+            MeteorSize::Small => 0.75,
+            MeteorSize::Tiny => 0.4,
+        }
+    }
+
+    // Damage dealt on hitting a ship, heavier and faster rocks hit harder
+    pub fn impact_damage(&self, closing_speed: f32) -> u16 {
+        (self.mass() * closing_speed / DEBRIS_IMPACT_SPEED)
+            .round()
+            .clamp(1.0, u16::MAX as f32) as u16
+    }
+
+    pub fn health(&self) -> u16 {
+        match self {
+            MeteorSize::Big => 6,
+            MeteorSize::Medium => 3,
+            MeteorSize::Small => 2,
+            MeteorSize::Tiny => 1,
+        }
+    }
+
+    // Smaller rocks are harder to hit, so they are worth more
+    pub fn score(&self) -> u32 {
+        match self {
+            MeteorSize::Big => 20,
+            MeteorSize::Medium => 30,
+            MeteorSize::Small => 50,
+            MeteorSize::Tiny => 80,
+        }
+    }
+
+    // What it breaks into, tiny ones just crumble
+    pub fn next(&self) -> Option<MeteorSize> {
+        match self {
+            MeteorSize::Big => Some(MeteorSize::Medium),
+            MeteorSize::Medium => Some(MeteorSize::Small),
+            MeteorSize::Small => Some(MeteorSize::Tiny),
+            MeteorSize::Tiny => None,
+        }
+    }
+}
+
+// Rock the player shot or blasted recently, ships it hits count as environmental kills
+#[derive(Component)]
+pub struct PlayerPushed(pub Timer);
+
+impl Default for PlayerPushed {
+    fn default() -> Self {
+        PlayerPushed(Timer::from_seconds(
+            ENVIRONMENTAL_KILL_WINDOW,
+            TimerMode::Once,
+        ))
+    }
+}
+
+#[derive(Component)]
+pub struct Debris {
+    pub size: MeteorSize,
+    // File name prefix such as `meteorBrown`, fragments keep the colour
+    pub colour: String,
+}
+
+// Read size and colour from a name like `meteorGrey_med2.png`
+fn parse_meteor(image: &Handle<Image>) -> (String, MeteorSize) {
+    let name = image
+        .path()
+        .and_then(|path| path.path().file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let (colour, rest) = name.split_once('_').unwrap_or(("meteorBrown", "big"));
+    let size = [
+        MeteorSize::Big,
+        MeteorSize::Medium,
+        MeteorSize::Small,
+        MeteorSize::Tiny,
+    ]
+    .into_iter()
+    .find(|size| rest.starts_with(size.name()))
+    .unwrap_or(MeteorSize::Big);
+    (colour.to_string(), size)
+}
+
+// Spawn a rock of whatever size its sprite shows
+pub fn spawn_meteor(
+    commands: &mut Commands,
+    image: Handle<Image>,
+    position: Vec2,
+    velocity: Vec2,
+    rng: &mut impl Rng,
+) {
+    let (colour, size) = parse_meteor(&image);
+
+    // Random rotation speed
+    let angular_velocity = rng.random_range(-2.0..=2.0);
+
+    // Solid bodies that bounce off each other. No collision events of their own, so
+    // rock on rock contacts stay with the physics and out of `handle_collisions`
+    commands.spawn((
+        RigidBody::Dynamic,
+        GravityScale(0.0),
+        Velocity {
+            linvel: velocity,
+            angvel: angular_velocity,
+        },
+        // Placeholder until the sprite's outline is traced
+        Collider::ball(size.radius()),
+        OutlineCollider,
+        ColliderMassProperties::Mass(size.mass()),
+        Restitution::coefficient(DEBRIS_RESTITUTION),
+        Friction::coefficient(0.3),
+        // Hits and blasts add to this, so several in one frame all count
+        ExternalImpulse::default(),
+        Transform::from_translation(position.extend(0.0)),
+        Sprite { image, ..default() },
+        Debris { size, colour },
+        Health::new(size.health()),
+    ));
+}
+
+// Screen edge a rock enters from
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum DebrisSide {
+    Top,
+    Left,
+    Right,
+}
+
+impl DebrisSide {
+    pub fn random(rng: &mut impl Rng) -> DebrisSide {
+        match rng.random_range(0..3) {
+            0 => DebrisSide::Top,
+            1 => DebrisSide::Left,
+            _ => DebrisSide::Right,
+        }
+    }
+}
+
+fn default_shower_warning() -> f32 {
+    2.0
+}
+
+fn default_shower_duration() -> f32 {
+    4.0
+}
+
+fn default_shower_lanes() -> u32 {
+    3
+}
+
+fn default_shower_speed() -> f32 {
+    250.0
+}
+
+// A dense storm from one edge, as written in level data
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct ShowerDef {
+    // Seconds the entry points are flagged before the first rock arrives
+    #[serde(default = "default_shower_warning")]
+    pub warning: f32,
+    // Seconds over which the rocks come in
+    #[serde(default = "default_shower_duration")]
+    pub duration: f32,
+    // Entry points along the edge, each with its own warning
+    #[serde(default = "default_shower_lanes")]
+    pub lanes: u32,
+    #[serde(default = "default_shower_speed")]
+    pub speed: f32,
+}
+
+// Meteor shower in progress, warning first and then streaming rocks in through its lanes
 #[derive(Component)]
-pub struct Debris;
+pub struct MeteorShower {
+    pub side: DebrisSide,
+    // Distance along the edge of each entry point
+    pub lanes: Vec<f32>,
+    pub speed: f32,
+    pub warning: Timer,
+    pub interval: Timer,
+    pub remaining: u32,
+}
+
+// Blinking marker at a shower's entry point
+#[derive(Component)]
+pub struct ShowerWarning(pub Entity);
+
+// Point just off the given edge and the direction pointing into the screen
+fn edge_entry(side: DebrisSide, along: f32) -> (Vec2, Vec2) {
+    match side {
+        DebrisSide::Top => (Vec2::new(along, WINDOW_HEIGHT / 2.0 + 50.0), Vec2::NEG_Y),
+        DebrisSide::Left => (Vec2::new(-WINDOW_WIDTH / 2.0 - 50.0, along), Vec2::X),
+        DebrisSide::Right => (Vec2::new(WINDOW_WIDTH / 2.0 + 50.0, along), Vec2::NEG_X),
+    }
+}
+
+// Announce a shower of `count` rocks from `side`, they start arriving once the warning is over
+pub fn start_meteor_shower(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    side: DebrisSide,
+    count: u32,
+    def: &ShowerDef,
+    rng: &mut impl Rng,
+) {
+    let edge = match side {
+        DebrisSide::Top => WINDOW_WIDTH,
+        DebrisSide::Left | DebrisSide::Right => WINDOW_HEIGHT,
+    };
+    let lanes: Vec<f32> = (0..def.lanes.max(1))
+        .map(|_| rng.random_range(-edge * 0.4..=edge * 0.4))
+        .collect();
+    let interval = def.duration / count.max(1) as f32;
+
+    let shower = commands
+        .spawn((
+            Transform::default(),
+            Visibility::default(),
+            MeteorShower {
+                side,
+                lanes: lanes.clone(),
+                speed: def.speed,
+                warning: Timer::from_seconds(def.warning, TimerMode::Once),
+                interval: Timer::from_seconds(interval.max(0.01), TimerMode::Repeating),
+                remaining: count,
+            },
+        ))
+        .id();
+
+    for along in lanes {
+        let (position, inward) = edge_entry(side, along);
+        commands.entity(shower).with_child((
+            Text2d::new("!"),
+            TextFont {
+                font: asset_server.load("Bonus/kenvector_future.ttf"),
+                font_size: 48.0,
+                ..default()
+            },
+            TextColor(HIT_FLASH_COLOR),
+            // Just inside the edge the rocks will come through
+            Transform::from_translation((position + inward * 90.0).extend(2.0)),
+            ShowerWarning(shower),
+        ));
+    }
+    commands.spawn(AudioPlayer::new(
+        asset_server.load("Bonus/sfx_shieldDown.ogg"),
+    ));
+}
+
+pub fn run_meteor_showers(
+    mut commands: Commands,
+    time: Res<Time>,
+    game_assets: Res<GameAssets>,
+    loaded_folders: Res<Assets<LoadedFolder>>,
+    mut shower_query: Query<(Entity, &mut MeteorShower)>,
+    mut warning_query: Query<(Entity, &ShowerWarning, &mut Visibility)>,
+) {
+    let mut rng = rand::rng();
+    for (entity, mut shower) in shower_query.iter_mut() {
+        if !shower.warning.tick(time.delta()).finished() {
+            let visible = ((shower.warning.elapsed_secs() * 6.0) as u32).is_multiple_of(2);
+            for (_, warning, mut visibility) in warning_query.iter_mut() {
+                if warning.0 == entity {
+                    *visibility = if visible {
+                        Visibility::Inherited
+                    } else {
+                        Visibility::Hidden
+                    };
+                }
+            }
+            continue;
+        }
+        if shower.warning.just_finished() {
+            for (warning_entity, warning, _) in warning_query.iter() {
+                if warning.0 == entity {
+                    commands.entity(warning_entity).despawn();
+                }
+            }
+        }
+
+        let Some(debris_folder) = loaded_folders.get(&game_assets.debris_folder) else {
+            continue;
+        };
+        if debris_folder.handles.is_empty() {
+            continue;
+        }
+
+        shower.interval.tick(time.delta());
+        let due = shower
+            .interval
+            .times_finished_this_tick()
+            .min(shower.remaining);
+        for _ in 0..due {
+            let along = *shower.lanes.choose(&mut rng).unwrap() + rng.random_range(-30.0..=30.0);
+            let (position, inward) = edge_entry(shower.side, along);
+            let drift = inward.perp() * rng.random_range(-40.0..=40.0);
+            let image = debris_folder
+                .handles
+                .choose(&mut rng)
+                .unwrap()
+                .clone()
+                .typed();
+            spawn_meteor(
+                &mut commands,
+                image,
+                position,
+                inward * shower.speed + drift,
+                &mut rng,
+            );
+        }
+        shower.remaining -= due;
+        if shower.remaining == 0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+// Spawn a single rock just off the given edge, drifting into the screen
+pub fn spawn_debris_rock(
+    commands: &mut Commands,
+    image: Handle<Image>,
+    side: DebrisSide,
+    rng: &mut impl Rng,
+) {
+    let (position, velocity) = match side {
+        DebrisSide::Top => {
+            // Spawn from top
+            let x = rng.random_range(-WINDOW_WIDTH / 2.0..=WINDOW_WIDTH / 2.0);
+            let pos = Vec3::new(x, WINDOW_HEIGHT / 2.0 + 50.0, 0.0);
+            let vel = Vec2::new(
+                rng.random_range(-50.0..=50.0),    // Random horizontal drift
+                rng.random_range(-200.0..=-100.0), // Downward speed
+            );
+            (pos, vel)
+        }
+        DebrisSide::Left => {
+            // Spawn from left
+            let y = rng.random_range(-WINDOW_HEIGHT / 2.0..=WINDOW_HEIGHT / 2.0);
+            let pos = Vec3::new(-WINDOW_WIDTH / 2.0 - 50.0, y, 0.0);
+            let vel = Vec2::new(
+                rng.random_range(100.0..=200.0), // Rightward speed
+                rng.random_range(-50.0..=50.0),  // Random vertical drift
+            );
+            (pos, vel)
+        }
+        DebrisSide::Right => {
+            // Spawn from right
+            let y = rng.random_range(-WINDOW_HEIGHT / 2.0..=WINDOW_HEIGHT / 2.0);
+            let pos = Vec3::new(WINDOW_WIDTH / 2.0 + 50.0, y, 0.0);
+            let vel = Vec2::new(
+                rng.random_range(-200.0..=-100.0), // Leftward speed
+                rng.random_range(-50.0..=50.0),    // Random vertical drift
+            );
+            (pos, vel)
+        }
+    };
+
+    spawn_meteor(commands, image, position.truncate(), velocity, rng);
+}
+
+// Outward shove for a rock caught in an explosion, fading to nothing at `radius`
+pub fn blast_impulse(center: Vec2, position: Vec2, radius: f32) -> Option<ExternalImpulse> {
+    let away = position - center;
+    let distance = away.length();
+    if distance >= radius {
+        return None;
+    }
+    Some(ExternalImpulse {
+        impulse: away.normalize_or(Vec2::Y) * DEBRIS_BLAST_IMPULSE * (1.0 - distance / radius),
+        torque_impulse: 0.0,
+    })
+}
 
 pub fn spawn_debris(
     mut commands: Commands,
@@ -15,11 +423,16 @@ pub fn spawn_debris(
     mut timer: ResMut<DebrisTimer>,
     game_asset: Res<GameAssets>,
     loaded_folders: Res<Assets<LoadedFolder>>,
+    shower_query: Query<(), With<MeteorShower>>,
 ) {
     // Only spawn if timer finished and debris folder is loaded
     if !timer.0.tick(time.delta()).just_finished() {
         return;
     }
+    // Hold the usual trickle back while a shower is on its way
+    if !shower_query.is_empty() {
+        return;
+    }
 
     if let Some(debris_folder) = loaded_folders.get(&game_asset.debris_folder) {
         if debris_folder.handles.is_empty() {
@@ -32,69 +445,11 @@ pub fn spawn_debris(
         for _ in 0..debris_spawn {
             // Get random debris image
             let random_index = rng.random_range(0..debris_folder.handles.len());
-            let random_handle = &debris_folder.handles[random_index];
-
-            // Convert to typed image handle
-            let image_handle = random_handle.clone();
-            {
-                // Choose random spawn side (0 = top, 1 = left, 2 = right)
-                let spawn_side = rng.random_range(0..3);
-
-                let (position, velocity) = match spawn_side {
-                    0 => {
-                        // Spawn from top
-                        let x = rng.random_range(-WINDOW_WIDTH / 2.0..=WINDOW_WIDTH / 2.0);
-                        let pos = Vec3::new(x, WINDOW_HEIGHT / 2.0 + 50.0, 0.0);
-                        let vel = Vec2::new(
-                            rng.random_range(-50.0..=50.0),    // Random horizontal drift
-                            rng.random_range(-200.0..=-100.0), // Downward speed
-                        );
-                        (pos, vel)
-                    }
-                    1 => {
-                        // Spawn from left
-                        let y = rng.random_range(-WINDOW_HEIGHT / 2.0..=WINDOW_HEIGHT / 2.0);
-                        let pos = Vec3::new(-WINDOW_WIDTH / 2.0 - 50.0, y, 0.0);
-                        let vel = Vec2::new(
-                            rng.random_range(100.0..=200.0), // Rightward speed
-                            rng.random_range(-50.0..=50.0),  // Random vertical drift
-                        );
-                        (pos, vel)
-                    }
-                    _ => {
-                        // Spawn from right
-                        let y = rng.random_range(-WINDOW_HEIGHT / 2.0..=WINDOW_HEIGHT / 2.0);
-                        let pos = Vec3::new(WINDOW_WIDTH / 2.0 + 50.0, y, 0.0);
-                        let vel = Vec2::new(
-                            rng.random_range(-200.0..=-100.0), // Leftward speed
-                            rng.random_range(-50.0..=50.0),    // Random vertical drift
-                        );
-                        (pos, vel)
-                    }
-                };
-
-                // Random size variation
-                let scale = rng.random_range(0.5..=1.5);
-
-                // Random rotation speed
-                let angular_velocity = rng.random_range(-2.0..=2.0);
-
-                commands.spawn((
-                    RigidBody::KinematicVelocityBased,
-                    Velocity {
-                        linvel: velocity,
-                        angvel: angular_velocity,
-                    },
-                    Collider::ball(32.0 * scale), // Adjust size based on your debris
-                    ActiveEvents::COLLISION_EVENTS,
-                    Transform::from_translation(position).with_scale(Vec3::splat(scale)),
-                    Sprite {
-                        image: image_handle.typed(),
-                        ..default()
-                    },
-                    Debris,
-                ));
-            }
+            let image_handle = debris_folder.handles[random_index].clone().typed();
+
+            // Choose random spawn side
+            let side = DebrisSide::random(&mut rng);
+            spawn_debris_rock(&mut commands, image_handle, side, &mut rng);
         }
     }
 }
@@ -117,3 +472,99 @@ pub fn cleanup_debris(
         }
     }
 }
+
+// Shot meteors split into two or three of the next size down, scattering from the parent's course
+pub fn apply_debris_damage(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
+    loaded_folders: Res<Assets<LoadedFolder>>,
+    mut damage_events: EventReader<DamageEvent>,
+    mut debris_query: Query<(&mut Health, &Debris, &Transform, &Velocity)>,
+    mut game_values: ResMut<GameValues>,
+) {
+    let mut rng = rand::rng();
+    for event in damage_events.read() {
+        let Ok((mut health, debris, transform, velocity)) = debris_query.get_mut(event.target)
+        else {
+            continue;
+        };
+        if health.current == 0 {
+            continue;
+        }
+
+        if !health.take(event.amount) {
+            commands.entity(event.target).insert(HitFlash::default());
+            continue;
+        }
+
+        game_values.score += debris.size.score();
+        commands.entity(event.target).try_despawn();
+        commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_zap.ogg")));
+
+        let Some(next) = debris.size.next() else {
+            continue;
+        };
+        let Some(debris_folder) = loaded_folders.get(&game_assets.debris_folder) else {
+            continue;
+        };
+        let prefix = format!("{}_{}", debris.colour, next.name());
+        let images: Vec<Handle<Image>> = debris_folder
+            .handles
+            .iter()
+            .filter(|handle| {
+                handle
+                    .path()
+                    .is_some_and(|path| path.to_string().contains(&prefix))
+            })
+            .map(|handle| handle.clone().typed())
+            .collect();
+        if images.is_empty() {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let count = rng.random_range(2..=3);
+        let heading = rng.random_range(0.0..std::f32::consts::TAU);
+        for i in 0..count {
+            // Evenly spread around a random heading, with some jitter
+            let angle = heading
+                + std::f32::consts::TAU * i as f32 / count as f32
+                + rng.random_range(-0.4..=0.4);
+            let direction = Vec2::from_angle(angle);
+            let spread = direction * rng.random_range(60.0..=120.0);
+            spawn_meteor(
+                &mut commands,
+                images.choose(&mut rng).unwrap().clone(),
+                // Far enough apart not to start out overlapping
+                position + direction * next.radius() * 1.2,
+                velocity.linvel + spread,
+                &mut rng,
+            );
+        }
+    }
+}
+
+pub fn tick_player_pushed(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pushed_query: Query<(Entity, &mut PlayerPushed)>,
+) {
+    for (entity, mut pushed) in pushed_query.iter_mut() {
+        if pushed.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<PlayerPushed>();
+        }
+    }
+}
+
+pub struct DebrisPlugin;
+
+impl Plugin for DebrisPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (apply_debris_damage, tick_player_pushed, run_meteor_showers)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
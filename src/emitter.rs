@@ -0,0 +1,244 @@
+use crate::{
+    player::PlayerMotion,
+    state::GameState,
+    weapon::{lead_direction, spawn_enemy_bullet},
+};
+use bevy::{prelude::*, sprite::Anchor};
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+use std::f32::consts::TAU;
+
+const EMITTER_BULLET: &str = "PNG/Lasers/laserRed08.png";
+const EMITTER_BULLET_SIZE: Vec2 = Vec2::new(10.0, 10.0);
+
+// Shapes a single volley can take. Angles are in degrees
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum EmitterPattern {
+    // Evenly spaced ring, starting straight down
+    Radial { count: u32 },
+    // Ring of `arms` that turns `turn_rate` degrees per second between volleys
+    Spiral { arms: u32, turn_rate: f32 },
+    // Fan centred on the player, or ahead of them with `lead`
+    AimedFan { count: u32, angle: f32 },
+    // Row of bullets falling straight down, with `gap` missing at a random spot
+    Wall { count: u32, spacing: f32, gap: u32 },
+    // Several patterns fired together as one volley
+    Combined(Vec<EmitterPattern>),
+}
+
+impl EmitterPattern {
+    // (offset from the emitter, direction) for every bullet in one volley
+    pub fn shots(&self, elapsed: f32, aim: Vec2, rng: &mut impl Rng) -> Vec<(Vec2, Vec2)> {
+        match self {
+            EmitterPattern::Radial { count } => ring(*count, 0.0),
+            EmitterPattern::Spiral { arms, turn_rate } => {
+                ring(*arms, (elapsed * turn_rate).to_radians())
+            }
+            EmitterPattern::AimedFan { count, angle } => {
+                let middle = (*count as f32 - 1.0) / 2.0;
+                (0..*count)
+                    .map(|i| {
+                        let rotation = ((i as f32 - middle) * angle).to_radians();
+                        (Vec2::ZERO, Vec2::from_angle(rotation).rotate(aim))
+                    })
+                    .collect()
+            }
+            EmitterPattern::Wall {
+                count,
+                spacing,
+                gap,
+            } => {
+                let gap = (*gap).min(*count);
+                let gap_start = rng.random_range(0..=count - gap);
+                let middle = (*count as f32 - 1.0) / 2.0;
+                (0..*count)
+                    .filter(|i| !(gap_start..gap_start + gap).contains(i))
+                    .map(|i| (Vec2::new((i as f32 - middle) * spacing, 0.0), Vec2::NEG_Y))
+                    .collect()
+            }
+            EmitterPattern::Combined(patterns) => patterns
+                .iter()
+                .flat_map(|pattern| pattern.shots(elapsed, aim, rng))
+                .collect(),
+        }
+    }
+}
+
+fn ring(count: u32, rotation: f32) -> Vec<(Vec2, Vec2)> {
+    (0..count)
+        .map(|i| {
+            let angle = rotation + TAU * i as f32 / count.max(1) as f32;
+            (Vec2::ZERO, Vec2::from_angle(angle).rotate(Vec2::NEG_Y))
+        })
+        .collect()
+}
+
+fn default_bullet_speed() -> f32 {
+    250.0
+}
+
+// One pattern on a timer, as written in level and boss data
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct EmitterDef {
+    pub pattern: EmitterPattern,
+    // Seconds between volleys
+    pub interval: f32,
+    #[serde(default = "default_bullet_speed")]
+    pub speed: f32,
+    // Bullets hang in place this long before moving
+    #[serde(default)]
+    pub delay: f32,
+    // Pixels per second squared, negative values slow bullets down and turn them back
+    #[serde(default)]
+    pub acceleration: f32,
+    // Where bullets leave the shooter, relative to its centre
+    #[serde(default)]
+    pub offset: (f32, f32),
+    // How far aimed patterns lead a moving player, 0 to 1
+    #[serde(default)]
+    pub lead: f32,
+}
+
+impl EmitterDef {
+    pub fn new(pattern: EmitterPattern, interval: f32) -> Self {
+        EmitterDef {
+            pattern,
+            interval,
+            speed: default_bullet_speed(),
+            delay: 0.0,
+            acceleration: 0.0,
+            offset: (0.0, 0.0),
+            lead: 0.0,
+        }
+    }
+}
+
+pub struct EmitterStage {
+    pub def: EmitterDef,
+    pub cooldown: Timer,
+}
+
+// Every stage fires on its own timer, so patterns layer on top of each other
+#[derive(Component)]
+pub struct Emitter {
+    pub stages: Vec<EmitterStage>,
+    pub elapsed: f32,
+}
+
+impl Emitter {
+    pub fn new(defs: Vec<EmitterDef>) -> Self {
+        Emitter {
+            stages: defs
+                .into_iter()
+                .map(|def| EmitterStage {
+                    cooldown: Timer::from_seconds(def.interval, TimerMode::Repeating),
+                    def,
+                })
+                .collect(),
+            elapsed: 0.0,
+        }
+    }
+}
+
+// Bullets that wait or change speed after being fired
+#[derive(Component)]
+pub struct BulletMotion {
+    pub delay: Timer,
+    pub direction: Vec2,
+    pub speed: f32,
+    pub acceleration: f32,
+}
+
+pub fn fire_emitters(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    mut emitter_query: Query<(&Transform, &mut Emitter)>,
+    player_query: Query<(&Transform, &PlayerMotion)>,
+) {
+    let mut rng = rand::rng();
+    let player = player_query
+        .single()
+        .ok()
+        .map(|(transform, motion)| (transform.translation.truncate(), motion.0));
+
+    for (transform, mut emitter) in emitter_query.iter_mut() {
+        emitter.elapsed += time.delta_secs();
+        let elapsed = emitter.elapsed;
+
+        for stage in emitter.stages.iter_mut() {
+            if !stage.cooldown.tick(time.delta()).just_finished() {
+                continue;
+            }
+
+            let def = &stage.def;
+            let origin = transform.translation.truncate() + Vec2::new(def.offset.0, def.offset.1);
+            let aim = player
+                .map(|(target, velocity)| {
+                    lead_direction(origin, target, velocity, def.speed, def.lead)
+                })
+                .filter(|aim| *aim != Vec2::ZERO)
+                .unwrap_or(Vec2::NEG_Y);
+
+            for (offset, direction) in def.pattern.shots(elapsed, aim, &mut rng) {
+                let moving = def.delay <= 0.0;
+                let entity = spawn_enemy_bullet(
+                    &mut commands,
+                    Collider::ball(EMITTER_BULLET_SIZE.x / 2.0),
+                    Transform::from_translation((origin + offset).extend(0.0)),
+                    Velocity {
+                        linvel: if moving {
+                            direction * def.speed
+                        } else {
+                            Vec2::ZERO
+                        },
+                        angvel: 0.0,
+                    },
+                    Sprite {
+                        image: asset_server.load(EMITTER_BULLET),
+                        custom_size: Some(EMITTER_BULLET_SIZE),
+                        anchor: Anchor::Center,
+                        ..default()
+                    },
+                    1,
+                );
+                if !moving || def.acceleration != 0.0 {
+                    commands.entity(entity).insert(BulletMotion {
+                        delay: Timer::from_seconds(def.delay, TimerMode::Once),
+                        direction,
+                        speed: def.speed,
+                        acceleration: def.acceleration,
+                    });
+                }
+            }
+            commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_laser2.ogg")));
+        }
+    }
+}
+
+pub fn update_bullet_motion(
+    time: Res<Time>,
+    mut bullet_query: Query<(&mut BulletMotion, &mut Velocity)>,
+) {
+    for (mut motion, mut velocity) in bullet_query.iter_mut() {
+        if !motion.delay.tick(time.delta()).finished() {
+            velocity.linvel = Vec2::ZERO;
+            continue;
+        }
+        // Decelerating bullets eventually reverse, so none are left hanging on screen
+        motion.speed += motion.acceleration * time.delta_secs();
+        velocity.linvel = motion.direction * motion.speed;
+    }
+}
+
+pub struct EmitterPlugin;
+
+impl Plugin for EmitterPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (fire_emitters, update_bullet_motion).run_if(in_state(GameState::Playing)),
+        );
+    }
+}
@@ -1,7 +1,17 @@
-use crate::{constant::*, player::Player};
-use bevy::{asset::LoadedFolder, prelude::*, sprite::Anchor};
+use crate::{
+    constant::*,
+    health::{DamageEvent, Health, HitFlash},
+    movement::MovementPattern,
+    outline::OutlineCollider,
+    path::PathFollower,
+    squad::SquadMember,
+    steering::Steering,
+    weapon::{AimMode, SpreadPattern, Weapon},
+};
+use bevy::{prelude::*, sprite::Anchor};
 use bevy_rapier2d::prelude::*;
-use rand::{Rng, seq::SliceRandom};
+use rand::Rng;
+use serde::Deserialize;
 
 #[derive(Component)]
 pub struct Enemy;
@@ -9,158 +19,317 @@ pub struct Enemy;
 #[derive(Component)]
 pub struct EnemyBullet;
 
-#[derive(Resource)]
-pub struct EnemySpawnTimer(pub Timer);
+// Just struck by a rock the player sent flying, a kill now earns the environmental bonus
+#[derive(Component)]
+pub struct EnvironmentalHit;
 
-#[derive(Resource)]
-pub struct EnemyBulletTimer(pub Timer);
+// Just struck by something the player had no hand in, a kill now earns nothing
+#[derive(Component)]
+pub struct NeutralHit;
 
-pub fn spawn_enemy(
-    mut commands: Commands,
-    time: Res<Time>,
-    mut timer: ResMut<EnemySpawnTimer>,
-    game_asset: Res<GameAssets>,
-    loaded_folders: Res<Assets<LoadedFolder>>,
-) {
-    if !timer.0.tick(time.delta()).just_finished() {
-        return;
+// Sent whenever the player destroys an enemy, so other systems can react (drops, effects)
+#[derive(Event)]
+pub struct EnemyDestroyed {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub kind: EnemyKind,
+}
+
+// Sprite colour tells the player what kind of ship they are facing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum EnemyColour {
+    // Slow, heavily armoured gunships
+    Black,
+    // Standard fighters
+    Blue,
+    // Fast, fragile interceptors that don't shoot
+    Green,
+    // Aggressive gunners with spread shots at higher tiers
+    Red,
+}
+
+impl EnemyColour {
+    pub const ALL: [EnemyColour; 4] = [
+        EnemyColour::Black,
+        EnemyColour::Blue,
+        EnemyColour::Green,
+        EnemyColour::Red,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EnemyColour::Black => "Black",
+            EnemyColour::Blue => "Blue",
+            EnemyColour::Green => "Green",
+            EnemyColour::Red => "Red",
+        }
     }
-    if let Some(enemies_folder) = loaded_folders.get(&game_asset.enemy_folder) {
-        if enemies_folder.handles.is_empty() {
-            return;
+}
+
+pub struct EnemyStats {
+    pub health: u8,
+    pub speed: f32,
+    // None for enemies that never shoot
+    pub fire_interval: Option<f32>,
+    pub spread: SpreadPattern,
+    pub score: u32,
+    pub drop_chance: f64,
+    // How far ahead of a moving player shots are aimed, 0 for none, 1 for a perfect intercept
+    pub lead: f32,
+}
+
+// One kind per sprite in `PNG/Enemies`, tier 1 to 5 within each colour
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub struct EnemyKind {
+    pub colour: EnemyColour,
+    pub tier: u8,
+}
+
+impl EnemyKind {
+    pub const MAX_TIER: u8 = 5;
+
+    pub fn random(rng: &mut impl Rng) -> EnemyKind {
+        EnemyKind {
+            colour: EnemyColour::ALL[rng.random_range(0..EnemyColour::ALL.len())],
+            tier: rng.random_range(1..=Self::MAX_TIER),
         }
+    }
+
+    pub fn sprite(&self) -> String {
+        format!("PNG/Enemies/enemy{}{}.png", self.colour.name(), self.tier)
+    }
+
+    pub fn stats(&self) -> EnemyStats {
+        // Higher tiers are tougher, faster, shoot more often and are worth more
+        let tier = (self.tier.clamp(1, Self::MAX_TIER) - 1) as f32;
+        let (health, speed, fire_interval, score, drop_chance, lead) = match self.colour {
+            EnemyColour::Black => (4.0, 120.0, Some(2.5), 20.0, 0.25, 0.2),
+            EnemyColour::Blue => (2.0, 200.0, Some(2.0), 10.0, 0.15, 0.1),
+            EnemyColour::Green => (1.0, 300.0, None, 8.0, 0.1, 0.0),
+            EnemyColour::Red => (2.0, 160.0, Some(1.4), 15.0, 0.15, 0.2),
+        };
+        let spread = match (self.colour, self.tier) {
+            (EnemyColour::Red, 3..) => SpreadPattern {
+                count: 3,
+                angle: 15.0,
+                spacing: 0.0,
+            },
+            _ => SpreadPattern::SINGLE,
+        };
 
-        let mut rng = rand::rng();
-        let rand_spawn = rng.random_range(MIN_ENEMY..=MAX_ENEMY);
-        let random_index = rng.random_range(0..enemies_folder.handles.len());
-
-        for _ in 0..rand_spawn {
-            // Generate random x position avoiding player area
-            let x = if rng.random_bool(0.5) {
-                // Left side
-                rng.random_range(-WINDOW_WIDTH / 2.0..=-PLAYER_SIZE.x * 2.0) + PLAYER_SIZE.x
-            } else {
-                // Right side
-                rng.random_range(PLAYER_SIZE.x * 2.0..=WINDOW_WIDTH / 2.0) - PLAYER_SIZE.x
-            };
-
-            commands.spawn((
-                RigidBody::Dynamic,
-                GravityScale(0.0),
-                Velocity {
-                    linvel: Vec2::new(0.0, -PLAYER_SPEED * 0.8),
-                    angvel: 0.0,
-                },
-                ActiveEvents::COLLISION_EVENTS,
-                Sensor,
-                Collider::cuboid(ENEMY_SIZE.x / 2.0, ENEMY_SIZE.y / 2.0),
-                Transform::from_xyz(x, WINDOW_HEIGHT / 2.0, 0.0),
-                Sprite {
-                    image: enemies_folder.handles[random_index].clone().typed(),
-                    custom_size: Some(ENEMY_SIZE),
-                    anchor: Anchor::Center,
-                    ..default()
-                },
-                Enemy,
-            ));
+        EnemyStats {
+            health: (health * (1.0 + tier * 0.5)) as u8,
+            speed: speed * (1.0 + tier * 0.05),
+            fire_interval: fire_interval.map(|interval: f32| interval * (1.0 - tier * 0.1)),
+            spread,
+            score: (score * (1.0 + tier)) as u32,
+            drop_chance,
+            // Top tier red and black ships hit a steadily moving player dead on
+            lead: (lead * (1.0 + tier)).min(1.0),
         }
     }
+
+    // Default flight pattern, waves may override it
+    pub fn movement(&self) -> MovementPattern {
+        match self.colour {
+            EnemyColour::Black => MovementPattern::StopAndHover {
+                amplitude: 10.0,
+                frequency: 0.25,
+            },
+            EnemyColour::Blue => MovementPattern::SineWeave {
+                amplitude: 60.0,
+                frequency: 0.5,
+            },
+            EnemyColour::Green if self.tier >= 3 => MovementPattern::SwoopAndExit {
+                amplitude: WINDOW_HEIGHT / 2.0,
+                frequency: 0.8,
+            },
+            EnemyColour::Green => MovementPattern::ZigZag {
+                amplitude: 80.0,
+                frequency: 0.75,
+            },
+            EnemyColour::Red => MovementPattern::StrafeThenDescend {
+                amplitude: 120.0,
+                frequency: 0.3,
+            },
+        }
+    }
+
+    // How the ship reacts to its surroundings on top of its flight pattern
+    pub fn steering(&self) -> Steering {
+        match self.colour {
+            // Gunships keep their distance from a player who closes in
+            EnemyColour::Black => Steering {
+                flee: 1.0,
+                flee_radius: 200.0,
+                ..default()
+            },
+            // Interceptors drift towards the player
+            EnemyColour::Green => Steering {
+                seek: 0.3,
+                ..default()
+            },
+            EnemyColour::Blue | EnemyColour::Red => Steering::default(),
+        }
+    }
+
+    pub fn weapon(&self) -> Option<Weapon> {
+        let stats = self.stats();
+        stats
+            .fire_interval
+            .map(|interval| enemy_weapon(interval, stats.spread, stats.lead))
+    }
+}
+
+// Enemy gun: a slow shot aimed at the player, more accurate and further led as `lead` rises
+pub fn enemy_weapon(fire_interval: f32, spread: SpreadPattern, lead: f32) -> Weapon {
+    let mut cooldown = Timer::from_seconds(fire_interval, TimerMode::Repeating);
+    // Stagger the first shot so a wave doesn't fire in unison
+    let mut rng = rand::rng();
+    cooldown.set_elapsed(std::time::Duration::from_secs_f32(
+        rng.random_range(0.0..fire_interval),
+    ));
+
+    Weapon {
+        cooldown,
+        projectile: "PNG/Lasers/laserRed07.png",
+        projectile_size: BULLET_SIZE,
+        speed: BULLET_SPEED * 0.6, // Enemy bullets are slower than player bullets
+        damage: 1,
+        spread,
+        aim: if lead > 0.0 {
+            AimMode::Lead {
+                accuracy: lead,
+                inaccuracy: 20.0 * (1.0 - lead),
+            }
+        } else {
+            AimMode::AtTarget { inaccuracy: 20.0 }
+        },
+        sound: Some("Bonus/sfx_laser2.ogg"),
+        muzzle: Vec2::new(0.0, -ENEMY_SIZE.y / 2.0),
+        firing: true,
+    }
+}
+
+// Spawn a ship of the given kind, callers add its `Movement` or `PathFollower`
+pub fn spawn_enemy_ship(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    kind: EnemyKind,
+    position: Vec2,
+) -> Entity {
+    let stats = kind.stats();
+    let mut enemy = commands.spawn((
+        RigidBody::Dynamic,
+        GravityScale(0.0),
+        Velocity {
+            linvel: Vec2::new(0.0, -stats.speed),
+            angvel: 0.0,
+        },
+        ActiveEvents::COLLISION_EVENTS,
+        Sensor,
+        Collider::cuboid(ENEMY_SIZE.x / 2.0, ENEMY_SIZE.y / 2.0),
+        OutlineCollider,
+        Transform::from_translation(position.extend(0.0)),
+        Sprite {
+            image: asset_server.load(kind.sprite()),
+            custom_size: Some(ENEMY_SIZE),
+            anchor: Anchor::Center,
+            ..default()
+        },
+        Enemy,
+        kind,
+        Health::new(stats.health as u16),
+        kind.steering(),
+    ));
+    if let Some(weapon) = kind.weapon() {
+        enemy.insert(weapon);
+    }
+    enemy.id()
 }
 
+#[allow(clippy::type_complexity)]
 pub fn cleanup_enemy(
     mut commands: Commands,
-    enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+    enemy_query: Query<
+        (
+            Entity,
+            &Transform,
+            &Velocity,
+            Has<PathFollower>,
+            Has<SquadMember>,
+        ),
+        With<Enemy>,
+    >,
 ) {
-    for (entity, transform) in enemy_query.iter() {
-        // Remove bullets that have gone off-screen
-        if transform.translation.y > WINDOW_HEIGHT / 2.0 + 50.0 {
+    for (entity, transform, velocity, on_path, in_squad) in enemy_query.iter() {
+        // Remove enemies that have gone off-screen, formations may still be entering from above
+        if transform.translation.y > WINDOW_HEIGHT / 2.0 + 50.0 && velocity.linvel.y > 0.0 {
             commands.entity(entity).despawn();
         }
         if transform.translation.y < -WINDOW_HEIGHT / 2.0 - 50.0 {
             commands.entity(entity).despawn();
         }
+        // Path followers may start and end beyond the side edges, divers may loop past them
+        if !on_path && !in_squad && transform.translation.x.abs() > WINDOW_WIDTH / 2.0 + 100.0 {
+            commands.entity(entity).despawn();
+        }
     }
 }
 
-pub fn enemy_shoot(
+// Apply queued damage to enemies, flashing on hits and destroying them at zero health
+#[allow(clippy::type_complexity)]
+pub fn apply_enemy_damage(
     mut commands: Commands,
-    enemy_query: Query<&Transform, With<Enemy>>,
-    player_query: Query<&Transform, With<Player>>,
     asset_server: Res<AssetServer>,
-    time: Res<Time>,
-    mut bullet_timer: ResMut<EnemyBulletTimer>,
+    mut damage_events: EventReader<DamageEvent>,
+    mut enemy_query: Query<
+        (
+            &mut Health,
+            &EnemyKind,
+            &Transform,
+            Has<EnvironmentalHit>,
+            Has<NeutralHit>,
+        ),
+        With<Enemy>,
+    >,
+    mut enemy_destroyed: EventWriter<EnemyDestroyed>,
+    mut game_values: ResMut<GameValues>,
 ) {
-    if bullet_timer.0.tick(time.delta()).just_finished() {
-        let mut rng = rand::rng();
-        let enemies: Vec<_> = enemy_query.iter().collect();
-
-        // Get player position - return early if no player exists
-        let Ok(player_transform) = player_query.single() else {
-            return;
+    for event in damage_events.read() {
+        let Ok((mut health, kind, transform, environmental, neutral)) =
+            enemy_query.get_mut(event.target)
+        else {
+            continue;
         };
-        let player_position = player_transform.translation.truncate(); // Convert Vec3 to Vec2
-
-        if !enemies.is_empty() {
-            // Only have 1-2 random enemies shoot each timer tick
-            let num_shooters = rng.random_range(1..=3.min(enemies.len()));
-            // Randomly select which enemies shoot
-            let mut indices: Vec<usize> = (0..enemies.len()).collect();
-            indices.shuffle(&mut rng);
-
-            for i in 0..num_shooters {
-                let enemy_transform = enemies[indices[i]];
-
-                // Get enemy position (where the bullet will spawn from)
-                let enemy_position = enemy_transform.translation.truncate();
-                if enemy_position.y < -WINDOW_HEIGHT * 0.8 {
-                    continue;
-                }
-                let bullet_spawn_position = enemy_position + Vec2::new(0.0, -ENEMY_SIZE.y / 2.0);
-
-                // Calculate direction vector from enemy to player
-                let direction = player_position - bullet_spawn_position;
-
-                // Normalize the direction to get a unit vector
-                let normalized_direction = direction.normalize();
-
-                // Calculate bullet velocity by multiplying direction with desired speed
-                let bullet_speed = BULLET_SPEED * 0.6; // Enemy bullets are slower than player bullets
-                let bullet_velocity = normalized_direction * bullet_speed;
-
-                //Add some inaccuracy to make it more interesting
-                let inaccuracy = 20.0; // Adjust this value to control accuracy
-                let random_offset = Vec2::new(
-                    rng.random_range(-inaccuracy..=inaccuracy),
-                    rng.random_range(-inaccuracy..=inaccuracy),
-                );
-                let final_velocity = bullet_velocity + random_offset;
-
-                commands.spawn((
-                    RigidBody::Dynamic,
-                    Collider::cuboid(BULLET_SIZE.x / 2.0, BULLET_SIZE.y / 2.0),
-                    Velocity {
-                        linvel: final_velocity, // Use calculated velocity instead of fixed downward movement
-                        angvel: 0.0,
-                    },
-                    Transform::from_translation(
-                        enemy_transform.translation + Vec3::new(0.0, -ENEMY_SIZE.y / 2.0, 0.0),
-                    ),
-                    Sprite {
-                        image: asset_server.load("PNG/Lasers/laserRed07.png"),
-                        custom_size: Some(BULLET_SIZE),
-                        anchor: Anchor::Center,
-                        ..default()
-                    },
-                    GravityScale(0.0),
-                    EnemyBullet,
-                    Bullet,
-                ));
-            }
+        // Already destroyed by an earlier hit this frame
+        if health.current == 0 {
+            continue;
+        }
 
-            // Play one sound effect per shooting round, not per bullet
-            if num_shooters > 0 {
-                commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_laser2.ogg")));
+        if health.take(event.amount) {
+            // Nothing the player did, so no score and no drop
+            if neutral && !environmental {
+                commands.entity(event.target).try_despawn();
+                continue;
+            }
+            enemy_destroyed.write(EnemyDestroyed {
+                entity: event.target,
+                position: transform.translation.truncate(),
+                kind: *kind,
+            });
+            game_values.score += kind.stats().score;
+            if environmental {
+                game_values.score += ENVIRONMENTAL_KILL_BONUS;
             }
+            commands.entity(event.target).try_despawn();
+            commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_zap.ogg")));
+        } else {
+            // Survived the hit, so neither marker carries over to a later kill
+            commands
+                .entity(event.target)
+                .insert(HitFlash::default())
+                .remove::<(EnvironmentalHit, NeutralHit)>();
         }
     }
 }
@@ -0,0 +1,70 @@
+use crate::state::GameState;
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct Health {
+    pub current: u16,
+    pub max: u16,
+}
+
+impl Health {
+    pub fn new(max: u16) -> Self {
+        Health { current: max, max }
+    }
+
+    pub fn fraction(&self) -> f32 {
+        self.current as f32 / self.max.max(1) as f32
+    }
+
+    // Returns true if this hit brought health down to zero
+    pub fn take(&mut self, amount: u16) -> bool {
+        self.current = self.current.saturating_sub(amount);
+        self.current == 0
+    }
+}
+
+// Request to damage an entity, handled by whichever system owns that entity type
+#[derive(Event)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub amount: u16,
+}
+
+// Tints the sprite for a moment after taking a hit
+#[derive(Component)]
+pub struct HitFlash(pub Timer);
+
+pub const HIT_FLASH_COLOR: Color = Color::srgb(1.0, 0.35, 0.35);
+pub const HIT_FLASH_TIME: f32 = 0.08;
+
+impl Default for HitFlash {
+    fn default() -> Self {
+        HitFlash(Timer::from_seconds(HIT_FLASH_TIME, TimerMode::Once))
+    }
+}
+
+pub fn update_hit_flash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flash_query: Query<(Entity, &mut HitFlash, &mut Sprite)>,
+) {
+    for (entity, mut flash, mut sprite) in flash_query.iter_mut() {
+        if flash.0.tick(time.delta()).finished() {
+            sprite.color = Color::WHITE;
+            commands.entity(entity).remove::<HitFlash>();
+        } else {
+            sprite.color = HIT_FLASH_COLOR;
+        }
+    }
+}
+
+pub struct HealthPlugin;
+
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DamageEvent>().add_systems(
+            Update,
+            update_hit_flash.run_if(in_state(GameState::Playing)),
+        );
+    }
+}
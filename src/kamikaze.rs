@@ -0,0 +1,210 @@
+use crate::{
+    constant::*,
+    debris::{Debris, blast_impulse},
+    enemy::{Enemy, NeutralHit, apply_enemy_damage},
+    health::{DamageEvent, HIT_FLASH_COLOR},
+    player::Player,
+    state::GameState,
+};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum KamikazeState {
+    // Flying onto the screen
+    Entering,
+    // Slowing to a stop and blinking, the player's warning to get clear
+    Arming(Timer),
+    // Locked on and accelerating, the fuse is burning
+    Homing,
+}
+
+// Ship that dives into the player and blows up instead of shooting
+#[derive(Component)]
+pub struct Kamikaze {
+    pub state: KamikazeState,
+    pub fuse: Timer,
+    pub speed: f32,
+}
+
+impl Kamikaze {
+    pub fn new(speed: f32) -> Self {
+        Kamikaze {
+            state: KamikazeState::Entering,
+            fuse: Timer::from_seconds(KAMIKAZE_FUSE, TimerMode::Once),
+            speed,
+        }
+    }
+}
+
+// Blow up this frame, from touching the player or a spent fuse
+#[derive(Component)]
+pub struct Detonate;
+
+// Expanding ring left by a detonation
+#[derive(Component)]
+pub struct KamikazeBlast(pub Timer);
+
+pub fn steer_kamikazes(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut gizmos: Gizmos,
+    mut kamikaze_query: Query<(
+        Entity,
+        &mut Kamikaze,
+        &Transform,
+        &mut Velocity,
+        &mut Sprite,
+    )>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let delta = time.delta_secs();
+    let player_position = player_query
+        .single()
+        .ok()
+        .map(|transform| transform.translation.truncate());
+
+    for (entity, kamikaze, transform, mut velocity, mut sprite) in kamikaze_query.iter_mut() {
+        let kamikaze = kamikaze.into_inner();
+        let position = transform.translation.truncate();
+        let speed = kamikaze.speed;
+
+        match &mut kamikaze.state {
+            KamikazeState::Entering => {
+                velocity.linvel = Vec2::new(0.0, -speed);
+                if position.y <= WINDOW_HEIGHT / 2.0 - ENEMY_SIZE.y * 2.0 {
+                    kamikaze.state = KamikazeState::Arming(Timer::from_seconds(
+                        KAMIKAZE_ARM_TIME,
+                        TimerMode::Once,
+                    ));
+                }
+            }
+            KamikazeState::Arming(timer) => {
+                velocity.linvel *= (1.0 - delta * 4.0).max(0.0);
+
+                // Blink faster as lock-on gets closer, with a line to the player
+                let blink = (timer.elapsed_secs() * (4.0 + timer.fraction() * 12.0)) as u32 % 2;
+                sprite.color = if blink == 0 {
+                    HIT_FLASH_COLOR
+                } else {
+                    Color::WHITE
+                };
+                if let Some(target) = player_position {
+                    gizmos.line_2d(position, target, HIT_FLASH_COLOR.with_alpha(0.4));
+                }
+
+                if timer.tick(time.delta()).finished() {
+                    sprite.color = HIT_FLASH_COLOR;
+                    kamikaze.state = KamikazeState::Homing;
+                    commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_twoTone.ogg")));
+                }
+            }
+            KamikazeState::Homing => {
+                if kamikaze.fuse.tick(time.delta()).finished() {
+                    commands.entity(entity).try_insert(Detonate);
+                    continue;
+                }
+
+                // Accelerate with a capped turn rate, so a sharp sidestep still shakes it off
+                let current_speed = (velocity.linvel.length() + KAMIKAZE_ACCELERATION * delta)
+                    .min(KAMIKAZE_MAX_SPEED);
+                let current = velocity.linvel.normalize_or(Vec2::NEG_Y);
+                let desired = player_position
+                    .map(|target| (target - position).normalize_or(current))
+                    .unwrap_or(current);
+                let max_turn = KAMIKAZE_TURN_RATE * delta;
+                let turn = current.angle_to(desired).clamp(-max_turn, max_turn);
+                let direction = Vec2::from_angle(turn).rotate(current);
+                velocity.linvel = direction * current_speed;
+            }
+        }
+    }
+}
+
+// Area damage to everything in range, including the player and other enemies
+#[allow(clippy::type_complexity)]
+pub fn detonate_kamikazes(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    kamikaze_query: Query<(Entity, &Transform), (With<Kamikaze>, With<Detonate>)>,
+    target_query: Query<(Entity, &Transform, Has<Enemy>), Or<(With<Player>, With<Enemy>)>>,
+    mut debris_query: Query<(&Transform, &mut ExternalImpulse), With<Debris>>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    for (kamikaze, transform) in kamikaze_query.iter() {
+        let center = transform.translation.truncate();
+        for (target, target_transform, is_enemy) in target_query.iter() {
+            if target != kamikaze
+                && target_transform.translation.truncate().distance(center) <= KAMIKAZE_BLAST_RADIUS
+            {
+                // Caught in another ship's blast, not the player's kill
+                if is_enemy {
+                    commands.entity(target).try_insert(NeutralHit);
+                }
+                damage_events.write(DamageEvent {
+                    target,
+                    amount: KAMIKAZE_DAMAGE,
+                });
+            }
+        }
+
+        // Rocks are thrown clear rather than damaged
+        for (rock_transform, mut impulse) in debris_query.iter_mut() {
+            let position = rock_transform.translation.truncate();
+            if let Some(blast) = blast_impulse(center, position, KAMIKAZE_BLAST_RADIUS * 2.0) {
+                *impulse += blast;
+            }
+        }
+
+        commands.entity(kamikaze).try_despawn();
+        commands.spawn((
+            Transform::from_translation(center.extend(1.0)),
+            Sprite {
+                image: asset_server.load("PNG/Effects/star3.png"),
+                custom_size: Some(ENEMY_SIZE),
+                color: HIT_FLASH_COLOR,
+                ..default()
+            },
+            KamikazeBlast(Timer::from_seconds(0.3, TimerMode::Once)),
+        ));
+        commands.spawn(AudioPlayer::new(
+            asset_server.load("Bonus/sfx_shieldDown.ogg"),
+        ));
+    }
+}
+
+pub fn update_kamikaze_blasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut blast_query: Query<(Entity, &mut KamikazeBlast, &mut Sprite)>,
+) {
+    for (entity, mut blast, mut sprite) in blast_query.iter_mut() {
+        if blast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        } else {
+            // Grow out to the blast radius while fading
+            let size =
+                ENEMY_SIZE.x + (KAMIKAZE_BLAST_RADIUS * 2.0 - ENEMY_SIZE.x) * blast.0.fraction();
+            sprite.custom_size = Some(Vec2::splat(size));
+            sprite.color.set_alpha(blast.0.fraction_remaining());
+        }
+    }
+}
+
+pub struct KamikazePlugin;
+
+impl Plugin for KamikazePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                steer_kamikazes,
+                // Mark blast victims before their damage is applied
+                detonate_kamikazes.before(apply_enemy_damage),
+                update_kamikaze_blasts,
+            )
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
@@ -0,0 +1,415 @@
+use crate::{
+    ai::{Brain, BrainProfile},
+    boss::{BossKind, spawn_boss},
+    constant::*,
+    data::RonAssetLoader,
+    debris::{DebrisSide, ShowerDef, spawn_debris_rock, start_meteor_shower},
+    emitter::{Emitter, EmitterDef},
+    enemy::{EnemyKind, spawn_enemy_ship},
+    kamikaze::Kamikaze,
+    movement::{Movement, MovementPattern},
+    path::{FlightPath, PathFollower},
+    squad::{Squad, SquadMember, SquadOrders, SquadState},
+    state::GameState,
+    turret::{TurretDef, TurretMount, spawn_turret},
+    weapon::Weapon,
+};
+use bevy::{asset::LoadedFolder, prelude::*};
+use rand::Rng;
+use serde::Deserialize;
+
+// How the ships of a group are laid out around their entry point
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+pub enum Formation {
+    // Side by side
+    #[default]
+    Line,
+    // One behind the other
+    Column,
+    // Arrowhead with the leader in front
+    V,
+    // Rows of `columns` ships
+    Grid {
+        columns: u32,
+    },
+}
+
+impl Formation {
+    pub fn offset(&self, index: u32, count: u32, spacing: f32) -> Vec2 {
+        let i = index as f32;
+        match self {
+            Formation::Line => Vec2::new((i - (count as f32 - 1.0) / 2.0) * spacing, 0.0),
+            Formation::Column => Vec2::new(0.0, i * spacing),
+            Formation::V => {
+                let row = index.div_ceil(2) as f32;
+                let side = if index % 2 == 1 { -1.0 } else { 1.0 };
+                Vec2::new(side * row * spacing, row * spacing * 0.6)
+            }
+            Formation::Grid { columns } => {
+                let columns = (*columns).max(1);
+                let column = (index % columns) as f32;
+                let row = (index / columns) as f32;
+                Vec2::new(
+                    (column - (columns as f32 - 1.0) / 2.0) * spacing,
+                    row * spacing,
+                )
+            }
+        }
+    }
+}
+
+fn default_spacing() -> f32 {
+    60.0
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnemyGroup {
+    pub kind: EnemyKind,
+    pub count: u32,
+    // Seconds after the wave starts
+    #[serde(default)]
+    pub delay: f32,
+    #[serde(default)]
+    pub formation: Formation,
+    #[serde(default = "default_spacing")]
+    pub spacing: f32,
+    // Where the formation enters, ignored when flying a path
+    #[serde(default)]
+    pub entry: (f32, f32),
+    // Overrides the kind's own movement pattern and speed
+    #[serde(default)]
+    pub movement: Option<MovementPattern>,
+    #[serde(default)]
+    pub speed: Option<f32>,
+    // Name of a flight path in `assets/paths`, without the extension
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub mirror: bool,
+    // Bullet patterns that replace the kind's own gun
+    #[serde(default)]
+    pub fire: Option<Vec<EmitterDef>>,
+    // Fly in as a squad that holds the formation and sends members on dives
+    #[serde(default)]
+    pub squad: Option<SquadOrders>,
+    // Ships hold fire and home in on the player to blow up, `movement` is ignored
+    #[serde(default)]
+    pub kamikaze: bool,
+    // Ships think for themselves, closing in, strafing and pulling out, `movement` is ignored
+    #[serde(default)]
+    pub brain: Option<BrainProfile>,
+    // Turrets mounted on every ship, turning them into carriers
+    #[serde(default)]
+    pub turrets: Vec<TurretDef>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DebrisEvent {
+    #[serde(default)]
+    pub delay: f32,
+    pub count: u32,
+    // Random edge per rock when not given, or per shower
+    #[serde(default)]
+    pub side: Option<DebrisSide>,
+    // Send the rocks as a warned, dense storm from one edge rather than all at once
+    #[serde(default)]
+    pub shower: Option<ShowerDef>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum WaveEnd {
+    // Move on after this many seconds, whatever is still alive
+    Timer(f32),
+    // Move on once every ship in the wave is gone
+    Cleared,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Wave {
+    pub groups: Vec<EnemyGroup>,
+    #[serde(default)]
+    pub debris: Vec<DebrisEvent>,
+    // Ground turrets that scroll in from the top edge
+    #[serde(default)]
+    pub turrets: Vec<TurretDef>,
+    // Boss that enters as soon as the wave starts
+    #[serde(default)]
+    pub boss: Option<BossKind>,
+    pub end: WaveEnd,
+}
+
+// A level as written in `assets/levels/*.level.ron`
+#[derive(Asset, TypePath, Debug, Clone, Deserialize)]
+pub struct Level {
+    pub name: String,
+    pub waves: Vec<Wave>,
+}
+
+// Tags ships with the wave that spawned them, so cleared waves can be detected
+#[derive(Component)]
+pub struct WaveMember(pub u32);
+
+#[derive(Resource)]
+pub struct LevelProgress {
+    pub level: Handle<Level>,
+    pub wave: usize,
+    pub wave_time: f32,
+    // Increases with every wave started, including repeats when the level loops
+    pub serial: u32,
+    pub groups_spawned: Vec<bool>,
+    pub debris_spawned: Vec<bool>,
+    pub turrets_spawned: Vec<bool>,
+    pub boss_spawned: bool,
+    // Ships of the current wave still alive, for the HUD
+    pub remaining: usize,
+}
+
+impl LevelProgress {
+    fn start_wave(&mut self, wave: usize) {
+        self.wave = wave;
+        self.wave_time = 0.0;
+        self.serial += 1;
+        self.groups_spawned.clear();
+        self.debris_spawned.clear();
+        self.turrets_spawned.clear();
+        self.boss_spawned = false;
+        self.remaining = 0;
+    }
+}
+
+pub fn load_level(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(LevelProgress {
+        level: asset_server.load("levels/level1.level.ron"),
+        wave: 0,
+        wave_time: 0.0,
+        serial: 0,
+        groups_spawned: Vec::new(),
+        debris_spawned: Vec::new(),
+        turrets_spawned: Vec::new(),
+        boss_spawned: false,
+        remaining: 0,
+    });
+}
+
+// New games start from the first wave
+pub fn reset_level(mut progress: ResMut<LevelProgress>) {
+    progress.start_wave(0);
+}
+
+// One ship of a group, tagged with its wave and armed with the group's patterns
+fn spawn_group_ship(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    group: &EnemyGroup,
+    position: Vec2,
+    serial: u32,
+) -> Entity {
+    let enemy = spawn_enemy_ship(commands, asset_server, group.kind, position);
+    commands.entity(enemy).insert(WaveMember(serial));
+    if let Some(fire) = &group.fire {
+        commands
+            .entity(enemy)
+            .remove::<Weapon>()
+            .insert(Emitter::new(fire.clone()));
+    }
+    for def in &group.turrets {
+        let offset = Vec2::new(def.offset.0, def.offset.1);
+        let turret = spawn_turret(commands, asset_server, def, position + offset);
+        commands.entity(turret).insert(TurretMount {
+            carrier: enemy,
+            offset,
+        });
+    }
+    enemy
+}
+
+// Spawn a group, returns false if it has to wait for its flight path to load
+fn spawn_group(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    paths: &Assets<FlightPath>,
+    group: &EnemyGroup,
+    serial: u32,
+) -> bool {
+    let stats = group.kind.stats();
+
+    if let Some(path_name) = &group.path {
+        let handle: Handle<FlightPath> = asset_server.load(format!("paths/{}.path.ron", path_name));
+        let Some(path) = paths.get(&handle) else {
+            return false;
+        };
+        for i in 0..group.count {
+            let follower = PathFollower {
+                path: handle.clone(),
+                // Ships queue up behind each other at the start of the path
+                distance: -(i as f32) * group.spacing,
+                mirror: group.mirror,
+            };
+            let position = follower.position(path);
+            let enemy = spawn_group_ship(commands, asset_server, group, position, serial);
+            commands.entity(enemy).insert(follower);
+        }
+        return true;
+    }
+
+    let entry = Vec2::new(group.entry.0, group.entry.1);
+    if let Some(orders) = group.squad {
+        // Slots are laid out by the formation around the squad's anchor
+        let squad = commands.spawn_empty().id();
+        let mut slots = Vec::new();
+        for i in 0..group.count {
+            let mut offset = group.formation.offset(i, group.count, group.spacing);
+            if group.mirror {
+                offset.x = -offset.x;
+            }
+            let enemy = spawn_group_ship(commands, asset_server, group, entry + offset, serial);
+            commands.entity(enemy).insert(SquadMember {
+                squad,
+                offset,
+                speed: group.speed.unwrap_or(stats.speed),
+                state: SquadState::Entering,
+            });
+            slots.push((offset, Some(enemy)));
+        }
+        commands.entity(squad).insert(Squad::new(orders, slots));
+        return true;
+    }
+
+    let pattern = group.movement.unwrap_or_else(|| group.kind.movement());
+    let speed = group.speed.unwrap_or(stats.speed);
+    for i in 0..group.count {
+        let mut position = entry + group.formation.offset(i, group.count, group.spacing);
+        if group.mirror {
+            position.x = -position.x;
+        }
+        let enemy = spawn_group_ship(commands, asset_server, group, position, serial);
+        if group.kamikaze {
+            commands
+                .entity(enemy)
+                .remove::<(Weapon, Emitter)>()
+                .insert(Kamikaze::new(speed));
+        } else if let Some(profile) = group.brain {
+            commands.entity(enemy).insert(Brain::new(profile, speed));
+        } else {
+            commands
+                .entity(enemy)
+                .insert(Movement::new(pattern, speed, position));
+        }
+    }
+    true
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_level(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
+    loaded_folders: Res<Assets<LoadedFolder>>,
+    levels: Res<Assets<Level>>,
+    paths: Res<Assets<FlightPath>>,
+    mut progress: ResMut<LevelProgress>,
+    member_query: Query<&WaveMember>,
+) {
+    let Some(level) = levels.get(&progress.level) else {
+        return;
+    };
+    if level.waves.is_empty() {
+        return;
+    }
+
+    let wave = &level.waves[progress.wave % level.waves.len()];
+    let serial = progress.serial;
+    progress.wave_time += time.delta_secs();
+    progress.groups_spawned.resize(wave.groups.len(), false);
+    progress.debris_spawned.resize(wave.debris.len(), false);
+    progress.turrets_spawned.resize(wave.turrets.len(), false);
+
+    // Checked before this frame's spawns, whose ships only exist once the commands
+    // are applied and so can't be counted by `member_query` until next frame
+    let all_spawned = progress.groups_spawned.iter().all(|&spawned| spawned)
+        && progress.turrets_spawned.iter().all(|&spawned| spawned)
+        && (wave.boss.is_none() || progress.boss_spawned);
+
+    for (i, group) in wave.groups.iter().enumerate() {
+        if !progress.groups_spawned[i] && progress.wave_time >= group.delay {
+            progress.groups_spawned[i] =
+                spawn_group(&mut commands, &asset_server, &paths, group, serial);
+        }
+    }
+
+    for (i, def) in wave.turrets.iter().enumerate() {
+        if !progress.turrets_spawned[i] && progress.wave_time >= def.delay {
+            let position = Vec2::new(def.offset.0, WINDOW_HEIGHT / 2.0 + 40.0 + def.offset.1);
+            let turret = spawn_turret(&mut commands, &asset_server, def, position);
+            commands.entity(turret).insert(WaveMember(serial));
+            progress.turrets_spawned[i] = true;
+        }
+    }
+
+    if let (Some(kind), false) = (wave.boss, progress.boss_spawned) {
+        let boss = spawn_boss(&mut commands, &asset_server, kind);
+        commands.entity(boss).insert(WaveMember(serial));
+        progress.boss_spawned = true;
+    }
+
+    if let Some(debris_folder) = loaded_folders.get(&game_assets.debris_folder) {
+        let mut rng = rand::rng();
+        for (i, event) in wave.debris.iter().enumerate() {
+            if progress.debris_spawned[i]
+                || progress.wave_time < event.delay
+                || debris_folder.handles.is_empty()
+            {
+                continue;
+            }
+            if let Some(shower) = &event.shower {
+                let side = event.side.unwrap_or_else(|| DebrisSide::random(&mut rng));
+                start_meteor_shower(
+                    &mut commands,
+                    &asset_server,
+                    side,
+                    event.count,
+                    shower,
+                    &mut rng,
+                );
+                progress.debris_spawned[i] = true;
+                continue;
+            }
+            for _ in 0..event.count {
+                let index = rng.random_range(0..debris_folder.handles.len());
+                let image = debris_folder.handles[index].clone().typed();
+                let side = event.side.unwrap_or_else(|| DebrisSide::random(&mut rng));
+                spawn_debris_rock(&mut commands, image, side, &mut rng);
+            }
+            progress.debris_spawned[i] = true;
+        }
+    }
+
+    progress.remaining = member_query
+        .iter()
+        .filter(|member| member.0 == serial)
+        .count();
+
+    let finished = match wave.end {
+        WaveEnd::Timer(seconds) => all_spawned && progress.wave_time >= seconds,
+        WaveEnd::Cleared => all_spawned && progress.remaining == 0,
+    };
+    if finished {
+        // The level loops back to its first wave after the last one
+        let next = (progress.wave + 1) % level.waves.len();
+        progress.start_wave(next);
+    }
+}
+
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Level>()
+            .register_asset_loader(RonAssetLoader::<Level>::new(&["level.ron"]))
+            .add_systems(Startup, load_level)
+            .add_systems(OnExit(GameState::MainMenu), reset_level)
+            .add_systems(OnExit(GameState::GameOver), reset_level)
+            .add_systems(Update, run_level.run_if(in_state(GameState::Playing)));
+    }
+}
@@ -1,6 +1,25 @@
+pub mod ai;
+pub mod beam;
+pub mod bomb;
+pub mod boss;
 pub mod constant;
+pub mod data;
+pub mod emitter;
 pub mod enemy;
+pub mod health;
+pub mod kamikaze;
+pub mod level;
+pub mod missile;
+pub mod movement;
+pub mod outline;
 pub mod player;
 pub mod collision;
 pub mod debris;
-pub mod state;
\ No newline at end of file
+pub mod path;
+pub mod pickup;
+pub mod squad;
+pub mod state;
+pub mod steering;
+pub mod turret;
+pub mod ufo;
+pub mod weapon;
@@ -1,8 +1,26 @@
 use bevy::prelude::*;
 use bevy::window::{PresentMode, WindowResolution};
 use bevy_rapier2d::prelude::*;
+use first_bevy_game::ai::*;
+use first_bevy_game::beam::*;
+use first_bevy_game::bomb::*;
+use first_bevy_game::boss::*;
 use first_bevy_game::collision::*;
 use first_bevy_game::debris::*;
+use first_bevy_game::emitter::*;
+use first_bevy_game::health::*;
+use first_bevy_game::kamikaze::*;
+use first_bevy_game::level::*;
+use first_bevy_game::missile::*;
+use first_bevy_game::movement::*;
+use first_bevy_game::outline::*;
+use first_bevy_game::path::*;
+use first_bevy_game::pickup::*;
+use first_bevy_game::squad::*;
+use first_bevy_game::steering::*;
+use first_bevy_game::turret::*;
+use first_bevy_game::ufo::*;
+use first_bevy_game::weapon::*;
 use first_bevy_game::{constant::*, enemy::*, player::*, state::*};
 
 fn main() {
@@ -23,19 +41,36 @@ fn main() {
             }),
         )
         .add_plugins(GameStatePlugin)
+        .add_plugins(HealthPlugin)
+        .add_plugins(PickupPlugin)
+        .add_plugins(WeaponPlugin)
+        .add_plugins(EmitterPlugin)
+        .add_plugins(BeamPlugin)
+        .add_plugins(MissilePlugin)
+        .add_plugins(BombPlugin)
+        .add_plugins(BossPlugin)
+        .add_plugins(MovementPlugin)
+        .add_plugins(PathPlugin)
+        .add_plugins(LevelPlugin)
+        .add_plugins(SquadPlugin)
+        .add_plugins(KamikazePlugin)
+        .add_plugins(TurretPlugin)
+        .add_plugins(AiPlugin)
+        .add_plugins(SteeringPlugin)
+        .add_plugins(UfoPlugin)
+        .add_plugins(DebrisPlugin)
+        .add_plugins(OutlinePlugin)
+        .add_event::<EnemyDestroyed>()
         .init_state::<GameState>()
-        .insert_resource(GameValues{score: 0, health: 10})
+        .insert_resource(GameValues {
+            score: 0,
+            health: 10,
+            weapon_level: 0,
+            missiles: MISSILE_AMMO,
+            bombs: BOMB_STOCK,
+        })
         .insert_resource(ClearColor(Color::srgb_u8(0, 0, 0)))
         .insert_resource(PlayerSpawned::default()) // Black background
-        .insert_resource(BulletTimer(Timer::from_seconds(0.1, TimerMode::Repeating)))
-        .insert_resource(EnemySpawnTimer(Timer::from_seconds(
-            2.,
-            TimerMode::Repeating,
-        )))
-        .insert_resource(EnemyBulletTimer(Timer::from_seconds(
-            0.5,
-            TimerMode::Repeating,
-        )))
         .insert_resource(DebrisTimer(Timer::from_seconds(2., TimerMode::Repeating)))
         .add_plugins(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0))
         // .add_plugins(RapierDebugRenderPlugin::default())
@@ -48,13 +83,15 @@ fn main() {
             (
                 spawn_player,
                 move_player,
-                player_shoot,
+                apply_knockback.before(move_player),
+                player_trigger.before(fire_weapons),
+                sync_player_weapon.before(fire_weapons),
                 cleanup_bullets,
-                enemy_shoot,
-                spawn_enemy.after(load_assets_once),
+                tick_invulnerability,
                 handle_collisions,
+                apply_enemy_damage.after(handle_collisions),
+                apply_player_damage,
                 cleanup_enemy,
-                handle_collisions,
                 spawn_debris,
                 cleanup_debris,
             )
@@ -0,0 +1,132 @@
+use crate::{constant::*, enemy::Enemy, player::Player, state::GameState, weapon::Damage};
+use bevy::{prelude::*, sprite::Anchor};
+use bevy_rapier2d::prelude::*;
+
+#[derive(Component)]
+pub struct Missile {
+    pub target: Option<Entity>,
+    pub lifetime: Timer,
+}
+
+fn nearest_enemy(
+    position: Vec2,
+    enemy_query: &Query<(Entity, &Transform), With<Enemy>>,
+) -> Option<Entity> {
+    enemy_query
+        .iter()
+        .min_by(|(_, a), (_, b)| {
+            let distance_a = a.translation.truncate().distance_squared(position);
+            let distance_b = b.translation.truncate().distance_squared(position);
+            distance_a.total_cmp(&distance_b)
+        })
+        .map(|(entity, _)| entity)
+}
+
+pub fn launch_missile(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    asset_server: Res<AssetServer>,
+    player_query: Query<&Transform, With<Player>>,
+    enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+    mut game_values: ResMut<GameValues>,
+) {
+    let launch_pressed = keyboard_input.just_pressed(KeyCode::KeyC)
+        || mouse_input.just_pressed(MouseButton::Middle);
+    if !launch_pressed || game_values.missiles == 0 {
+        return;
+    }
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+
+    let position = player_transform.translation.truncate() + Vec2::new(0.0, PLAYER_SIZE.y / 2.0);
+    game_values.missiles -= 1;
+
+    commands.spawn((
+        RigidBody::KinematicVelocityBased,
+        Collider::cuboid(MISSILE_SIZE.x / 2.0, MISSILE_SIZE.y / 2.0),
+        Transform::from_translation(position.extend(0.0)),
+        Velocity {
+            linvel: Vec2::new(0.0, MISSILE_SPEED),
+            angvel: 0.0,
+        },
+        Sprite {
+            image: asset_server.load("PNG/Lasers/laserGreen08.png"),
+            custom_size: Some(MISSILE_SIZE),
+            anchor: Anchor::Center,
+            ..default()
+        },
+        Sensor,
+        ActiveEvents::COLLISION_EVENTS,
+        Damage(MISSILE_DAMAGE),
+        Missile {
+            target: nearest_enemy(position, &enemy_query),
+            lifetime: Timer::from_seconds(MISSILE_LIFETIME, TimerMode::Once),
+        },
+    ));
+    commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_laser2.ogg")));
+}
+
+pub fn steer_missiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut missile_query: Query<
+        (Entity, &mut Missile, &mut Transform, &mut Velocity),
+        Without<Enemy>,
+    >,
+    enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+) {
+    for (entity, mut missile, mut transform, mut velocity) in missile_query.iter_mut() {
+        if missile.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+
+        // Pick a new target if the old one was destroyed
+        if missile.target.is_none_or(|target| !enemy_query.contains(target)) {
+            missile.target = nearest_enemy(position, &enemy_query);
+        }
+        let target_position = missile
+            .target
+            .and_then(|target| enemy_query.get(target).ok())
+            .map(|(_, target_transform)| target_transform.translation.truncate());
+
+        // Turn toward the target, limited by the missile's turn rate
+        if let Some(target_position) = target_position {
+            let heading = velocity.linvel.normalize_or(Vec2::Y);
+            let desired = (target_position - position).normalize_or(heading);
+            let max_turn = MISSILE_TURN_RATE * time.delta_secs();
+            let turn = heading.angle_to(desired).clamp(-max_turn, max_turn);
+            velocity.linvel = Vec2::from_angle(turn).rotate(heading) * MISSILE_SPEED;
+        }
+
+        transform.rotation = Quat::from_rotation_z(Vec2::Y.angle_to(velocity.linvel));
+    }
+}
+
+pub fn cleanup_missiles(
+    mut commands: Commands,
+    missile_query: Query<(Entity, &Transform), With<Missile>>,
+) {
+    for (entity, transform) in missile_query.iter() {
+        let pos = transform.translation;
+        if pos.x.abs() > WINDOW_WIDTH / 2.0 + 50.0 || pos.y.abs() > WINDOW_HEIGHT / 2.0 + 50.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub struct MissilePlugin;
+
+impl Plugin for MissilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (launch_missile, steer_missiles, cleanup_missiles)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
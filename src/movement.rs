@@ -0,0 +1,132 @@
+use crate::{constant::*, state::GameState};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::Deserialize;
+use std::f32::consts::{PI, TAU};
+
+// Parametric flight patterns. Amplitude is in pixels, frequency in cycles per second
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum MovementPattern {
+    // Straight down
+    Straight,
+    // Smooth side to side weave while descending
+    SineWeave { amplitude: f32, frequency: f32 },
+    // Sharp diagonal switchbacks while descending
+    ZigZag { amplitude: f32, frequency: f32 },
+    // Dive `amplitude` pixels, then curve away and leave off the top of the screen
+    SwoopAndExit { amplitude: f32, frequency: f32 },
+    // Descend to the hold line, sway sideways for one cycle, then continue down
+    StrafeThenDescend { amplitude: f32, frequency: f32 },
+    // Descend to the hold line, bob in place for one cycle, then continue down
+    StopAndHover { amplitude: f32, frequency: f32 },
+}
+
+#[derive(Component)]
+pub struct Movement {
+    pub pattern: MovementPattern,
+    pub speed: f32,
+    pub elapsed: f32,
+    // Spawn position, patterns are relative to it
+    pub origin: Vec2,
+    // When the pattern reached its second phase (turn, strafe or hover)
+    pub phase_started: Option<f32>,
+}
+
+impl Movement {
+    pub fn new(pattern: MovementPattern, speed: f32, origin: Vec2) -> Self {
+        Movement {
+            pattern,
+            speed,
+            elapsed: 0.0,
+            origin,
+            phase_started: None,
+        }
+    }
+
+    pub fn velocity(&mut self, position: Vec2) -> Vec2 {
+        let t = self.elapsed;
+        let down = Vec2::new(0.0, -self.speed);
+
+        match self.pattern {
+            MovementPattern::Straight => down,
+            MovementPattern::SineWeave {
+                amplitude,
+                frequency,
+            } => {
+                let w = TAU * frequency;
+                Vec2::new(amplitude * w * (w * t).cos(), -self.speed)
+            }
+            MovementPattern::ZigZag {
+                amplitude,
+                frequency,
+            } => {
+                // Cover 2 * amplitude every half cycle, starting from the centre of the sweep
+                let half_cycle = (t * frequency * 2.0 + 0.5).floor() as i32;
+                let direction = if half_cycle % 2 == 0 { 1.0 } else { -1.0 };
+                Vec2::new(direction * 4.0 * amplitude * frequency, -self.speed)
+            }
+            MovementPattern::SwoopAndExit {
+                amplitude,
+                frequency,
+            } => {
+                if self.phase_started.is_none() && self.origin.y - position.y < amplitude {
+                    return down;
+                }
+                let started = *self.phase_started.get_or_insert(t);
+                // Turn half a circle, away from the side we came in on
+                let angle = ((t - started) * frequency * PI).min(PI);
+                let side = if self.origin.x > 0.0 { -1.0 } else { 1.0 };
+                Vec2::new(side * angle.sin(), -angle.cos()) * self.speed
+            }
+            MovementPattern::StrafeThenDescend {
+                amplitude,
+                frequency,
+            } => match self.hold_time(position, t) {
+                Some(held) if held < 1.0 / frequency => {
+                    let w = TAU * frequency;
+                    Vec2::new(amplitude * w * (w * held).cos(), 0.0)
+                }
+                _ => down,
+            },
+            MovementPattern::StopAndHover {
+                amplitude,
+                frequency,
+            } => match self.hold_time(position, t) {
+                Some(held) if held < 1.0 / frequency => {
+                    let w = TAU * frequency;
+                    Vec2::new(0.0, amplitude * w * (w * held).cos())
+                }
+                _ => down,
+            },
+        }
+    }
+
+    // Seconds spent since reaching the hold line, None while still descending to it
+    fn hold_time(&mut self, position: Vec2, t: f32) -> Option<f32> {
+        if self.phase_started.is_none() && position.y > ENEMY_HOLD_Y {
+            return None;
+        }
+        Some(t - *self.phase_started.get_or_insert(t))
+    }
+}
+
+pub fn apply_movement_patterns(
+    time: Res<Time>,
+    mut movement_query: Query<(&mut Movement, &Transform, &mut Velocity)>,
+) {
+    for (mut movement, transform, mut velocity) in movement_query.iter_mut() {
+        movement.elapsed += time.delta_secs();
+        velocity.linvel = movement.velocity(transform.translation.truncate());
+    }
+}
+
+pub struct MovementPlugin;
+
+impl Plugin for MovementPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            apply_movement_patterns.run_if(in_state(GameState::Playing)),
+        );
+    }
+}
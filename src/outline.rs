@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::collections::HashMap;
+
+// Pixels at least this opaque count as part of the shape
+const ALPHA_THRESHOLD: f32 = 0.5;
+
+// Swap the entity's placeholder collider for a hull traced from its sprite once the image loads
+#[derive(Component)]
+pub struct OutlineCollider;
+
+// Convex hull of each image's opaque pixels, in pixels around the image centre
+#[derive(Resource, Default)]
+pub struct OutlineCache(pub HashMap<AssetId<Image>, Option<Vec<Vec2>>>);
+
+// Opaque pixels next to a transparent one or the image edge, as pixel corners around the centre
+fn trace_outline(image: &Image) -> Vec<Vec2> {
+    let (width, height) = (image.width(), image.height());
+    let opaque = |x: i64, y: i64| {
+        x >= 0
+            && y >= 0
+            && x < width as i64
+            && y < height as i64
+            && image
+                .get_color_at(x as u32, y as u32)
+                .is_ok_and(|color| color.alpha() >= ALPHA_THRESHOLD)
+    };
+
+    let half = Vec2::new(width as f32, height as f32) / 2.0;
+    let mut points = Vec::new();
+    for y in 0..height as i64 {
+        for x in 0..width as i64 {
+            if !opaque(x, y) {
+                continue;
+            }
+            let edge =
+                !opaque(x - 1, y) || !opaque(x + 1, y) || !opaque(x, y - 1) || !opaque(x, y + 1);
+            if edge {
+                // Image rows run top to bottom, world y runs up
+                let corner = Vec2::new(x as f32, -(y as f32)) + Vec2::new(-half.x, half.y);
+                points.extend([
+                    corner,
+                    corner + Vec2::X,
+                    corner - Vec2::Y,
+                    corner + Vec2::X - Vec2::Y,
+                ]);
+            }
+        }
+    }
+    points
+}
+
+fn cross(o: Vec2, a: Vec2, b: Vec2) -> f32 {
+    (a - o).perp_dot(b - o)
+}
+
+// Monotone chain hull, counter-clockwise without repeating the first point
+fn convex_hull(mut points: Vec<Vec2>) -> Option<Vec<Vec2>> {
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+    if points.len() < 3 {
+        return None;
+    }
+
+    // Lower half left to right, then upper half right to left
+    let reversed: Vec<Vec2> = points.iter().rev().copied().collect();
+    let mut hull: Vec<Vec2> = Vec::with_capacity(points.len() * 2);
+    for pass in [&points, &reversed] {
+        let start = hull.len();
+        for &point in pass {
+            while hull.len() >= start + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        // The last point of each half starts the other one
+        hull.pop();
+    }
+    (hull.len() >= 3).then_some(hull)
+}
+
+pub fn apply_outline_colliders(
+    mut commands: Commands,
+    images: Res<Assets<Image>>,
+    mut cache: ResMut<OutlineCache>,
+    outline_query: Query<(Entity, &Sprite), With<OutlineCollider>>,
+) {
+    for (entity, sprite) in outline_query.iter() {
+        let id = sprite.image.id();
+        // Not loaded yet, keep the placeholder for now
+        let Some(image) = images.get(id) else {
+            continue;
+        };
+        let hull = cache
+            .0
+            .entry(id)
+            .or_insert_with(|| convex_hull(trace_outline(image)));
+
+        // Fully transparent images keep their placeholder
+        if let Some(hull) = hull {
+            let mut scale = sprite
+                .custom_size
+                .map_or(Vec2::ONE, |size| size / image.size_f32());
+            if sprite.flip_x {
+                scale.x = -scale.x;
+            }
+            if sprite.flip_y {
+                scale.y = -scale.y;
+            }
+            let points: Vec<Vec2> = hull.iter().map(|point| *point * scale).collect();
+            if let Some(collider) = Collider::convex_hull(&points) {
+                commands.entity(entity).try_insert(collider);
+            }
+        }
+        commands.entity(entity).remove::<OutlineCollider>();
+    }
+}
+
+pub struct OutlinePlugin;
+
+impl Plugin for OutlinePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<OutlineCache>()
+            .add_systems(Update, apply_outline_colliders);
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{
+    data::RonAssetLoader,
+    movement::{Movement, MovementPattern},
+    state::GameState,
+};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::Deserialize;
+
+// Segments each curve section is flattened into
+const SAMPLES_PER_SEGMENT: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum CurveKind {
+    // Chained cubic Bezier sections, 3n + 1 control points
+    Bezier,
+    // Smooth curve passing through every point
+    CatmullRom,
+}
+
+// What a follower does once it runs out of path
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+pub enum PathExit {
+    Despawn,
+    // Keep flying along the last heading
+    Continue,
+    // Fall back to flying straight down
+    #[default]
+    Descend,
+}
+
+// A flight path as written in `assets/paths/*.path.ron`, in world coordinates
+#[derive(Deserialize)]
+pub struct FlightPathDef {
+    pub curve: CurveKind,
+    pub points: Vec<(f32, f32)>,
+    pub speed: f32,
+    // Join the last point back to the first
+    #[serde(default)]
+    pub closed: bool,
+    // Times to fly the path before exiting, only useful on closed paths
+    #[serde(default = "default_laps")]
+    pub laps: u32,
+    #[serde(default)]
+    pub exit: PathExit,
+}
+
+fn default_laps() -> u32 {
+    1
+}
+
+#[derive(Asset, TypePath, Debug, Clone, Deserialize)]
+#[serde(from = "FlightPathDef")]
+pub struct FlightPath {
+    pub speed: f32,
+    pub closed: bool,
+    pub laps: u32,
+    pub exit: PathExit,
+    // Curve flattened into short segments so followers can move at a constant speed
+    pub polyline: Vec<Vec2>,
+    // Distance along the path at each polyline point
+    pub distances: Vec<f32>,
+}
+
+fn catmull_rom(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2, t: f32) -> Vec2 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * (2.0 * p1
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+fn bezier(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2, t: f32) -> Vec2 {
+    let u = 1.0 - t;
+    u * u * u * p0 + 3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t * p3
+}
+
+impl From<FlightPathDef> for FlightPath {
+    fn from(def: FlightPathDef) -> Self {
+        let points: Vec<Vec2> = def.points.iter().map(|&(x, y)| Vec2::new(x, y)).collect();
+        let mut polyline = Vec::new();
+
+        match def.curve {
+            CurveKind::CatmullRom if points.len() >= 2 => {
+                let count = points.len() as isize;
+                // Open paths clamp at their ends, closed ones wrap around
+                let point = |i: isize| {
+                    if def.closed {
+                        points[i.rem_euclid(count) as usize]
+                    } else {
+                        points[i.clamp(0, count - 1) as usize]
+                    }
+                };
+                let segments = if def.closed { count } else { count - 1 };
+                for i in 0..segments {
+                    for step in 0..SAMPLES_PER_SEGMENT {
+                        let t = step as f32 / SAMPLES_PER_SEGMENT as f32;
+                        polyline.push(catmull_rom(
+                            point(i - 1),
+                            point(i),
+                            point(i + 1),
+                            point(i + 2),
+                            t,
+                        ));
+                    }
+                }
+                polyline.push(point(segments));
+            }
+            CurveKind::Bezier if points.len() >= 4 => {
+                for section in points.windows(4).step_by(3) {
+                    for step in 0..SAMPLES_PER_SEGMENT {
+                        let t = step as f32 / SAMPLES_PER_SEGMENT as f32;
+                        polyline.push(bezier(section[0], section[1], section[2], section[3], t));
+                    }
+                }
+                let last = (points.len() - 1) / 3 * 3;
+                polyline.push(points[last]);
+            }
+            // Too few points for a curve, fly straight between them
+            _ => polyline = points,
+        }
+
+        let mut distances = Vec::with_capacity(polyline.len());
+        let mut total = 0.0;
+        for (i, point) in polyline.iter().enumerate() {
+            if i > 0 {
+                total += point.distance(polyline[i - 1]);
+            }
+            distances.push(total);
+        }
+
+        FlightPath {
+            speed: def.speed,
+            closed: def.closed,
+            laps: def.laps.max(1),
+            exit: def.exit,
+            polyline,
+            distances,
+        }
+    }
+}
+
+impl FlightPath {
+    pub fn length(&self) -> f32 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    pub fn start(&self) -> Vec2 {
+        self.polyline.first().copied().unwrap_or(Vec2::ZERO)
+    }
+
+    // Position after travelling `distance` along the path, wrapping on every lap
+    pub fn point_at(&self, distance: f32) -> Vec2 {
+        let length = self.length();
+        if self.polyline.len() < 2 || length <= 0.0 {
+            return self.start();
+        }
+
+        let distance = distance.max(0.0) % length;
+        let next = self
+            .distances
+            .partition_point(|&d| d <= distance)
+            .clamp(1, self.polyline.len() - 1);
+        let (d0, d1) = (self.distances[next - 1], self.distances[next]);
+        let t = if d1 > d0 { (distance - d0) / (d1 - d0) } else { 0.0 };
+        self.polyline[next - 1].lerp(self.polyline[next], t)
+    }
+}
+
+#[derive(Component)]
+pub struct PathFollower {
+    pub path: Handle<FlightPath>,
+    // Distance travelled so far, negative while waiting to enter
+    pub distance: f32,
+    // Fly the path flipped left to right
+    pub mirror: bool,
+}
+
+impl PathFollower {
+    pub fn position(&self, path: &FlightPath) -> Vec2 {
+        let point = path.point_at(self.distance);
+        if self.mirror {
+            Vec2::new(-point.x, point.y)
+        } else {
+            point
+        }
+    }
+}
+
+pub fn follow_paths(
+    mut commands: Commands,
+    time: Res<Time>,
+    paths: Res<Assets<FlightPath>>,
+    mut follower_query: Query<(Entity, &mut PathFollower, &Transform, &mut Velocity)>,
+) {
+    let delta = time.delta_secs();
+    if delta <= 0.0 {
+        return;
+    }
+
+    for (entity, mut follower, transform, mut velocity) in follower_query.iter_mut() {
+        let Some(path) = paths.get(&follower.path) else {
+            continue;
+        };
+
+        follower.distance += path.speed * delta;
+        if follower.distance < 0.0 {
+            velocity.linvel = Vec2::ZERO;
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        if follower.distance >= path.length() * path.laps as f32 {
+            commands.entity(entity).remove::<PathFollower>();
+            match path.exit {
+                PathExit::Despawn => commands.entity(entity).despawn(),
+                PathExit::Continue => {}
+                PathExit::Descend => {
+                    commands.entity(entity).insert(Movement::new(
+                        MovementPattern::Straight,
+                        path.speed,
+                        position,
+                    ));
+                }
+            }
+            continue;
+        }
+
+        // Steer exactly onto the next point so the physics body tracks the curve
+        velocity.linvel = (follower.position(path) - position) / delta;
+    }
+}
+
+pub struct PathPlugin;
+
+impl Plugin for PathPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<FlightPath>()
+            .register_asset_loader(RonAssetLoader::<FlightPath>::new(&["path.ron"]))
+            .add_systems(Update, follow_paths.run_if(in_state(GameState::Playing)));
+    }
+}
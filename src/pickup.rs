@@ -0,0 +1,92 @@
+use crate::{constant::*, enemy::EnemyDestroyed, state::GameState};
+use bevy::{prelude::*, sprite::Anchor};
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PickupKind {
+    WeaponUpgrade,
+    MissileAmmo,
+    Bomb,
+}
+
+impl PickupKind {
+    pub fn sprite(&self) -> &'static str {
+        match self {
+            PickupKind::WeaponUpgrade => "PNG/Power-ups/powerupBlue_bolt.png",
+            PickupKind::MissileAmmo => "PNG/Power-ups/things_gold.png",
+            PickupKind::Bomb => "PNG/Power-ups/powerupRed_star.png",
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Pickup(pub PickupKind);
+
+pub fn spawn_pickup(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    kind: PickupKind,
+    position: Vec2,
+) {
+    commands.spawn((
+        RigidBody::Dynamic,
+        GravityScale(0.0),
+        Velocity {
+            linvel: Vec2::new(0.0, -PICKUP_SPEED),
+            angvel: 0.0,
+        },
+        ActiveEvents::COLLISION_EVENTS,
+        Sensor,
+        Collider::cuboid(PICKUP_SIZE.x / 2.0, PICKUP_SIZE.y / 2.0),
+        Transform::from_translation(position.extend(0.0)),
+        Sprite {
+            image: asset_server.load(kind.sprite()),
+            custom_size: Some(PICKUP_SIZE),
+            anchor: Anchor::Center,
+            ..default()
+        },
+        Pickup(kind),
+    ));
+}
+
+// Destroyed enemies have a chance to leave a pickup behind
+pub fn drop_pickups(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut destroyed_events: EventReader<EnemyDestroyed>,
+) {
+    let mut rng = rand::rng();
+    for event in destroyed_events.read() {
+        if rng.random_bool(event.kind.stats().drop_chance) {
+            let kind = match rng.random_range(0..10) {
+                0..5 => PickupKind::WeaponUpgrade,
+                5..8 => PickupKind::MissileAmmo,
+                _ => PickupKind::Bomb,
+            };
+            spawn_pickup(&mut commands, &asset_server, kind, event.position);
+        }
+    }
+}
+
+pub fn cleanup_pickups(
+    mut commands: Commands,
+    pickup_query: Query<(Entity, &Transform), With<Pickup>>,
+) {
+    for (entity, transform) in pickup_query.iter() {
+        if transform.translation.y < -WINDOW_HEIGHT / 2.0 - 50.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub struct PickupPlugin;
+
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (drop_pickups, cleanup_pickups).run_if(in_state(GameState::Playing)),
+        );
+    }
+}
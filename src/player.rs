@@ -1,5 +1,12 @@
 use super::constant::*;
-use crate::enemy::EnemyBullet;
+use crate::{
+    beam::BeamCannon,
+    enemy::EnemyBullet,
+    health::DamageEvent,
+    outline::OutlineCollider,
+    state::GameState,
+    weapon::{AimMode, SpreadPattern, Weapon},
+};
 use bevy::{asset::LoadedFolder, prelude::*, sprite::Anchor};
 use bevy_rapier2d::prelude::*;
 use rand::Rng;
@@ -7,15 +14,102 @@ use rand::Rng;
 #[derive(Component)]
 pub struct Player;
 
-#[derive(Resource)]
-pub struct BulletTimer(pub Timer);
-
 #[derive(Component)]
 pub struct PlayerBullet;
 
+// How fast the player actually moved last frame, for enemies that lead their shots
+#[derive(Component, Default)]
+pub struct PlayerMotion(pub Vec2);
+
+// Player takes no damage until the timer runs out
+#[derive(Component)]
+pub struct Invulnerable(pub Timer);
+
+// Shove from an impact, fading out over a moment
+#[derive(Component)]
+pub struct Knockback(pub Vec2);
+
 #[derive(Resource, Default)]
 pub struct PlayerSpawned(pub bool);
 
+// Weapon level the player's `Weapon` was last built from
+#[derive(Component)]
+pub struct WeaponLevel(pub u8);
+
+// One entry per weapon level, gained from pickups and reset on death
+pub struct WeaponTier {
+    pub cooldown: f32,
+    pub sprite: &'static str,
+    pub spread: SpreadPattern,
+}
+
+pub const WEAPON_TIERS: [WeaponTier; 5] = [
+    // Single laser
+    WeaponTier {
+        cooldown: 0.1,
+        sprite: "PNG/Lasers/laserBlue01.png",
+        spread: SpreadPattern::SINGLE,
+    },
+    // Twin lasers
+    WeaponTier {
+        cooldown: 0.1,
+        sprite: "PNG/Lasers/laserBlue07.png",
+        spread: SpreadPattern {
+            count: 2,
+            angle: 0.0,
+            spacing: 20.0,
+        },
+    },
+    // 3-way spread
+    WeaponTier {
+        cooldown: 0.1,
+        sprite: "PNG/Lasers/laserGreen11.png",
+        spread: SpreadPattern {
+            count: 3,
+            angle: 12.0,
+            spacing: 8.0,
+        },
+    },
+    // 5-way spread
+    WeaponTier {
+        cooldown: 0.1,
+        sprite: "PNG/Lasers/laserGreen13.png",
+        spread: SpreadPattern {
+            count: 5,
+            angle: 11.0,
+            spacing: 7.0,
+        },
+    },
+    // 5-way spread with rapid fire
+    WeaponTier {
+        cooldown: 0.06,
+        sprite: "PNG/Lasers/laserRed01.png",
+        spread: SpreadPattern {
+            count: 5,
+            angle: 11.0,
+            spacing: 7.0,
+        },
+    },
+];
+
+pub const MAX_WEAPON_LEVEL: u8 = (WEAPON_TIERS.len() - 1) as u8;
+
+pub fn player_weapon(level: u8) -> Weapon {
+    let tier = &WEAPON_TIERS[level.min(MAX_WEAPON_LEVEL) as usize];
+    Weapon {
+        cooldown: Timer::from_seconds(tier.cooldown, TimerMode::Repeating),
+        projectile: tier.sprite,
+        projectile_size: BULLET_SIZE,
+        speed: BULLET_SPEED,
+        damage: 1,
+        spread: tier.spread,
+        aim: AimMode::Fixed(Vec2::Y),
+        sound: Some("Bonus/sfx_laser1.ogg"),
+        muzzle: Vec2::new(0.0, PLAYER_SIZE.y / 2.0),
+        firing: false,
+    }
+}
+
 pub fn spawn_player(
     mut commands: Commands,
     game_assets: Res<GameAssets>,
@@ -28,25 +122,39 @@ pub fn spawn_player(
     }
 
     if let Some(player_folder) = loaded_folder.get(&game_assets.player_folder) {
-
-        if player_folder.handles.is_empty() {
+        // The ufo sprites in the same folder belong to the bonus saucer
+        let ships: Vec<_> = player_folder
+            .handles
+            .iter()
+            .filter(|handle| {
+                handle
+                    .path()
+                    .is_some_and(|path| !path.to_string().contains("ufo"))
+            })
+            .collect();
+        if ships.is_empty() {
             return;
         }
         let mut rng = rand::rng();
-        let random_index = rng.random_range(0..player_folder.handles.len());
+        let random_index = rng.random_range(0..ships.len());
         commands.spawn((
             RigidBody::KinematicPositionBased, // Better for manual movement
             ActiveEvents::COLLISION_EVENTS,
             Collider::cuboid(PLAYER_SIZE.x / 2.0, PLAYER_SIZE.y / 2.0),
+            OutlineCollider,
             Transform::from_xyz(0.0, -WINDOW_HEIGHT / 2.0 + 100.0, 0.0),
             Sprite {
-                image: player_folder.handles[random_index].clone().typed(),
+                image: ships[random_index].clone().typed(),
                 custom_size: Some(PLAYER_SIZE),
                 anchor: Anchor::Center,
                 ..default()
             },
             Sensor,
             Player,
+            player_weapon(0),
+            WeaponLevel(0),
+            BeamCannon::default(),
+            PlayerMotion::default(),
         ));
         player_spawned.0 = true;
     } else {
@@ -56,10 +164,11 @@ pub fn spawn_player(
 
 pub fn move_player(
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut query: Query<&mut Transform, With<Player>>,
+    mut query: Query<(&mut Transform, &mut PlayerMotion), With<Player>>,
     time: Res<Time>,
 ) {
-    if let Ok(mut transform) = query.single_mut() {
+    if let Ok((mut transform, mut motion)) = query.single_mut() {
+        let start = transform.translation.truncate();
         let mut direction = Vec2::ZERO;
 
         // Calculate movement direction
@@ -93,47 +202,38 @@ pub fn move_player(
                 WINDOW_HEIGHT / 2.0 - PLAYER_SIZE.y / 2.0,
             );
         }
+
+        // Measured after clamping, so a player pinned to the edge reads as standing still
+        if time.delta_secs() > 0.0 {
+            motion.0 = (transform.translation.truncate() - start) / time.delta_secs();
+        }
     }
 }
 
-pub fn player_shoot(
-    mut commands: Commands,
+// Hold the player's trigger while fire is pressed, `fire_weapons` does the shooting
+pub fn player_trigger(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mouse_input: Res<ButtonInput<MouseButton>>,
-    player_query: Query<&Transform, With<Player>>,
-    asset_server: Res<AssetServer>,
-    time: Res<Time>,
-    mut bullet_timer: ResMut<BulletTimer>,
+    mut weapon_query: Query<&mut Weapon, With<Player>>,
 ) {
-    if bullet_timer.0.tick(time.delta()).just_finished() {
-        if keyboard_input.pressed(KeyCode::Space) || mouse_input.pressed(MouseButton::Left) {
-            if let Ok(player_transform) = player_query.single() {
-                // Spawn bullet at player position
-                commands.spawn((
-                    RigidBody::KinematicVelocityBased,
-                    Collider::cuboid(BULLET_SIZE.x / 2.0, BULLET_SIZE.y / 2.0),
-                    Transform::from_translation(
-                        player_transform.translation + Vec3::new(0.0, PLAYER_SIZE.y / 2.0, 0.0),
-                    ),
-                    Velocity {
-                        linvel: Vec2::new(0.0, BULLET_SPEED),
-                        angvel: 0.0,
-                    },
-                    Sprite {
-                        image: asset_server.load("PNG/Lasers/laserBlue01.png"),
-                        custom_size: Some(BULLET_SIZE),
-                        anchor: Anchor::Center,
-                        ..default()
-                    },
-                    Sensor,
-                    Bullet,
-                    PlayerBullet,
-                    ActiveEvents::COLLISION_EVENTS,
-                ));
-
-                commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_laser1.ogg")));
-            }
-        }
+    if let Ok(mut weapon) = weapon_query.single_mut() {
+        weapon.firing =
+            keyboard_input.pressed(KeyCode::Space) || mouse_input.pressed(MouseButton::Left);
+    }
+}
+
+// Rebuild the player's weapon when pickups or death change the weapon level
+pub fn sync_player_weapon(
+    game_values: Res<GameValues>,
+    mut player_query: Query<(&mut Weapon, &mut WeaponLevel), With<Player>>,
+) {
+    if let Ok((mut weapon, mut level)) = player_query.single_mut()
+        && level.0 != game_values.weapon_level
+    {
+        let firing = weapon.firing;
+        *weapon = player_weapon(game_values.weapon_level);
+        weapon.firing = firing;
+        level.0 = game_values.weapon_level;
     }
 }
 
@@ -144,15 +244,90 @@ pub fn cleanup_bullets(
 ) {
     // Clean up player bullets
     for (entity, transform) in player_bullet_query.iter() {
-        if transform.translation.y > WINDOW_HEIGHT / 2.0 + 50.0 {
+        if transform.translation.y > WINDOW_HEIGHT / 2.0 + 50.0
+            || transform.translation.x.abs() > WINDOW_WIDTH / 2.0 + 50.0
+        {
             commands.entity(entity).despawn();
         }
     }
 
     // Clean up enemy bullets
     for (entity, transform) in enemy_bullet_query.iter() {
-        if transform.translation.y < -WINDOW_HEIGHT / 2.0 - 50.0 {
+        if transform.translation.y < -WINDOW_HEIGHT / 2.0 - 50.0
+            || transform.translation.y > WINDOW_HEIGHT / 2.0 + 50.0
+            || transform.translation.x.abs() > WINDOW_WIDTH / 2.0 + 50.0
+        {
             commands.entity(entity).despawn();
         }
     }
 }
+
+// Slide the ship along its knockback, easing off until it is gone
+pub fn apply_knockback(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut player_query: Query<(Entity, &mut Transform, &mut Knockback), With<Player>>,
+) {
+    for (entity, mut transform, mut knockback) in player_query.iter_mut() {
+        transform.translation += (knockback.0 * time.delta_secs()).extend(0.0);
+        transform.translation.x = transform.translation.x.clamp(
+            -WINDOW_WIDTH / 2.0 + PLAYER_SIZE.x / 2.0,
+            WINDOW_WIDTH / 2.0 - PLAYER_SIZE.x / 2.0,
+        );
+        transform.translation.y = transform.translation.y.clamp(
+            -WINDOW_HEIGHT / 2.0 + PLAYER_SIZE.y / 2.0,
+            WINDOW_HEIGHT / 2.0 - PLAYER_SIZE.y / 2.0,
+        );
+
+        knockback.0 *= (1.0 - KNOCKBACK_DAMPING * time.delta_secs()).max(0.0);
+        if knockback.0.length() < 10.0 {
+            commands.entity(entity).remove::<Knockback>();
+        }
+    }
+}
+
+// Blink the ship while invulnerable and remove the component once it expires
+pub fn tick_invulnerability(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut player_query: Query<(Entity, &mut Invulnerable, &mut Sprite), With<Player>>,
+) {
+    for (entity, mut invulnerable, mut sprite) in player_query.iter_mut() {
+        if invulnerable.0.tick(time.delta()).finished() {
+            sprite.color.set_alpha(1.0);
+            commands.entity(entity).remove::<Invulnerable>();
+        } else {
+            let visible = ((invulnerable.0.elapsed_secs() * 10.0) as u32).is_multiple_of(2);
+            sprite.color.set_alpha(if visible { 1.0 } else { 0.3 });
+        }
+    }
+}
+
+// Damage aimed at the player from outside the collision handler, such as blasts
+pub fn apply_player_damage(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut damage_events: EventReader<DamageEvent>,
+    player_query: Query<Entity, (With<Player>, Without<Invulnerable>)>,
+    mut game_values: ResMut<GameValues>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for event in damage_events.read() {
+        if !player_query.contains(event.target) || game_values.health == 0 {
+            continue;
+        }
+
+        let damage = event.amount.min(u8::MAX as u16) as u8;
+        if game_values.health <= damage {
+            game_values.health = 0;
+            game_values.weapon_level = 0;
+            commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_lose.ogg")));
+            println!("Player died! Setting state to GameOver");
+            next_state.set(GameState::GameOver);
+            commands.entity(event.target).try_despawn();
+        } else {
+            game_values.health -= damage;
+            commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_zap.ogg")));
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{constant::*, player::Player, state::GameState};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::seq::IteratorRandom;
+use serde::Deserialize;
+use std::f32::consts::TAU;
+
+fn default_sway() -> f32 {
+    60.0
+}
+
+fn default_dive_interval() -> f32 {
+    3.0
+}
+
+fn default_divers() -> u32 {
+    1
+}
+
+// How a squad behaves once its members are in place, as written in level data
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct SquadOrders {
+    // Side to side shift of the whole formation in pixels
+    #[serde(default = "default_sway")]
+    pub sway: f32,
+    // Seconds between dives
+    #[serde(default = "default_dive_interval")]
+    pub dive_interval: f32,
+    // Members sent on each dive
+    #[serde(default = "default_divers")]
+    pub divers: u32,
+}
+
+// Owns the slots of a formation, members fly to `anchor + offset`
+#[derive(Component)]
+pub struct Squad {
+    pub orders: SquadOrders,
+    pub anchor: Vec2,
+    pub elapsed: f32,
+    pub dive_timer: Timer,
+    // Slot offsets and whoever still holds them
+    pub slots: Vec<(Vec2, Option<Entity>)>,
+}
+
+impl Squad {
+    pub fn new(orders: SquadOrders, slots: Vec<(Vec2, Option<Entity>)>) -> Self {
+        Squad {
+            orders,
+            anchor: Vec2::new(0.0, ENEMY_HOLD_Y),
+            elapsed: 0.0,
+            dive_timer: Timer::from_seconds(orders.dive_interval, TimerMode::Repeating),
+            slots,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SquadState {
+    // Flying in to the slot for the first time
+    Entering,
+    Holding,
+    // Looping down towards where the player was when the dive started
+    Diving { target: Vec2, elapsed: f32 },
+    // Survived a dive, heading back to the slot
+    Returning,
+}
+
+#[derive(Component)]
+pub struct SquadMember {
+    pub squad: Entity,
+    pub offset: Vec2,
+    pub speed: f32,
+    pub state: SquadState,
+}
+
+// Sway the formation, forget dead members and send the living on dives
+pub fn command_squads(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut squad_query: Query<(Entity, &mut Squad)>,
+    mut member_query: Query<&mut SquadMember>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let mut rng = rand::rng();
+    let player_position = player_query
+        .single()
+        .ok()
+        .map(|transform| transform.translation.truncate());
+
+    for (entity, mut squad) in squad_query.iter_mut() {
+        for (_, member) in squad.slots.iter_mut() {
+            if member.is_some_and(|member| !member_query.contains(member)) {
+                *member = None;
+            }
+        }
+        if squad.slots.iter().all(|(_, member)| member.is_none()) {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        squad.elapsed += time.delta_secs();
+        let w = TAU * SQUAD_SWAY_FREQUENCY;
+        squad.anchor.x = squad.orders.sway * (w * squad.elapsed).sin();
+
+        let Some(target) = player_position else {
+            continue;
+        };
+        if !squad.dive_timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+        let holding: Vec<Entity> = squad
+            .slots
+            .iter()
+            .filter_map(|(_, member)| *member)
+            .filter(|&member| {
+                member_query
+                    .get(member)
+                    .is_ok_and(|member| member.state == SquadState::Holding)
+            })
+            .collect();
+        for diver in holding
+            .into_iter()
+            .choose_multiple(&mut rng, squad.orders.divers as usize)
+        {
+            if let Ok(mut member) = member_query.get_mut(diver) {
+                member.state = SquadState::Diving {
+                    target,
+                    elapsed: 0.0,
+                };
+            }
+        }
+    }
+}
+
+pub fn fly_squad_members(
+    time: Res<Time>,
+    squad_query: Query<&Squad>,
+    mut member_query: Query<(&mut SquadMember, &Transform, &mut Velocity)>,
+) {
+    let delta = time.delta_secs();
+    if delta <= 0.0 {
+        return;
+    }
+
+    for (mut member, transform, mut velocity) in member_query.iter_mut() {
+        let Ok(squad) = squad_query.get(member.squad) else {
+            continue;
+        };
+        let position = transform.translation.truncate();
+        let slot = squad.anchor + member.offset;
+        let speed = member.speed;
+
+        match member.state {
+            SquadState::Entering | SquadState::Returning => {
+                let to_slot = slot - position;
+                if to_slot.length() <= speed * delta {
+                    member.state = SquadState::Holding;
+                    velocity.linvel = to_slot / delta;
+                } else {
+                    velocity.linvel = to_slot.normalize() * speed;
+                }
+            }
+            // Steer exactly onto the slot as the formation shifts, like path followers do
+            SquadState::Holding => velocity.linvel = (slot - position) / delta,
+            SquadState::Diving { target, elapsed } => {
+                let elapsed = elapsed + delta;
+                // Pull up once past the target, or if the loop drags on
+                if position.y < target.y || elapsed > SQUAD_DIVE_TIME {
+                    member.state = SquadState::Returning;
+                    continue;
+                }
+                member.state = SquadState::Diving { target, elapsed };
+
+                // Start by climbing away, the turn rate limit then swings it round into a loop
+                let dive_speed = speed * SQUAD_DIVE_SPEEDUP;
+                let current = velocity.linvel.normalize_or(Vec2::Y);
+                let current = if elapsed <= delta { Vec2::Y } else { current };
+                let desired = (target - position).normalize_or(Vec2::NEG_Y);
+                let max_turn = SQUAD_DIVE_TURN_RATE * delta;
+                let turn = current.angle_to(desired).clamp(-max_turn, max_turn);
+                velocity.linvel = Vec2::from_angle(turn).rotate(current) * dive_speed;
+            }
+        }
+    }
+}
+
+pub struct SquadPlugin;
+
+impl Plugin for SquadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (command_squads, fly_squad_members).run_if(in_state(GameState::Playing)),
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use bevy::{color::palettes::css::WHITE, prelude::*};
 
-use crate::constant::GameValues;
+use crate::{
+    constant::{BOMB_STOCK, GameValues, MISSILE_AMMO},
+    level::{Level, LevelProgress, WaveEnd},
+};
 
 // Define your game states
 #[derive(States, Default, Clone, Eq, PartialEq, Debug, Hash)]
@@ -149,6 +152,24 @@ pub fn setup_game_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
                 ScoreText,
             ));
 
+            // Wave progress display (top centre)
+            parent.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(40.0),
+                    top: Val::Px(20.0),
+                    ..default()
+                },
+                Text::new("Wave 1"),
+                TextFont {
+                    font: asset_server.load("Bonus/kenvector_future.ttf"),
+                    font_size: 24.0,
+                    ..Default::default()
+                },
+                TextColor(WHITE.into()),
+                WaveText,
+            ));
+
             // Health display (top right)
             parent.spawn((
                 Node {
@@ -166,6 +187,42 @@ pub fn setup_game_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
                 TextColor(WHITE.into()),
                 HealthText,
             ));
+
+            // Missile ammo display (below health)
+            parent.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(20.0),
+                    top: Val::Px(50.0),
+                    ..default()
+                },
+                Text::new("Missiles: 0"),
+                TextFont {
+                    font: asset_server.load("Bonus/kenvector_future.ttf"),
+                    font_size: 24.0,
+                    ..Default::default()
+                },
+                TextColor(WHITE.into()),
+                MissileText,
+            ));
+
+            // Bomb stock display (below missiles)
+            parent.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(20.0),
+                    top: Val::Px(80.0),
+                    ..default()
+                },
+                Text::new("Bombs: 0"),
+                TextFont {
+                    font: asset_server.load("Bonus/kenvector_future.ttf"),
+                    font_size: 24.0,
+                    ..Default::default()
+                },
+                TextColor(WHITE.into()),
+                BombText,
+            ));
         });
 }
 
@@ -386,6 +443,15 @@ pub struct ScoreText;
 #[derive(Component)]
 pub struct HealthText;
 
+#[derive(Component)]
+pub struct MissileText;
+
+#[derive(Component)]
+pub struct BombText;
+
+#[derive(Component)]
+pub struct WaveText;
+
 // Button interaction systems
 pub fn handle_play_button(
     mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<PlayButton>)>,
@@ -396,6 +462,9 @@ pub fn handle_play_button(
         if *interaction == Interaction::Pressed {
             game_values.score = 0;
             game_values.health = 10;
+            game_values.weapon_level = 0;
+            game_values.missiles = MISSILE_AMMO;
+            game_values.bombs = BOMB_STOCK;
             next_state.set(GameState::Playing);
         }
     }
@@ -471,6 +540,48 @@ pub fn update_health_display(
     }
 }
 
+pub fn update_missile_display(
+    mut query: Query<&mut Text, With<MissileText>>,
+    game_values: Res<GameValues>,
+) {
+    for mut text in query.iter_mut() {
+        text.0 = format!("Missiles: {}", game_values.missiles);
+    }
+}
+
+pub fn update_bomb_display(
+    mut query: Query<&mut Text, With<BombText>>,
+    game_values: Res<GameValues>,
+) {
+    for mut text in query.iter_mut() {
+        text.0 = format!("Bombs: {}", game_values.bombs);
+    }
+}
+
+pub fn update_wave_display(
+    mut query: Query<&mut Text, With<WaveText>>,
+    progress: Res<LevelProgress>,
+    levels: Res<Assets<Level>>,
+) {
+    let Some(level) = levels.get(&progress.level) else {
+        return;
+    };
+    let Some(wave) = level.waves.get(progress.wave) else {
+        return;
+    };
+    for mut text in query.iter_mut() {
+        text.0 = match wave.end {
+            WaveEnd::Cleared => format!(
+                "Wave {}/{} - {} left",
+                progress.wave + 1,
+                level.waves.len(),
+                progress.remaining
+            ),
+            WaveEnd::Timer(_) => format!("Wave {}/{}", progress.wave + 1, level.waves.len()),
+        };
+    }
+}
+
 // Example of how to register everything in main.rs
 //
 //
@@ -498,6 +609,9 @@ impl Plugin for GameStatePlugin {
                         .run_if(in_state(GameState::Playing).or(in_state(GameState::Paused))),
                     update_score_display.run_if(in_state(GameState::Playing)),
                     update_health_display.run_if(in_state(GameState::Playing)),
+                    update_missile_display.run_if(in_state(GameState::Playing)),
+                    update_bomb_display.run_if(in_state(GameState::Playing)),
+                    update_wave_display.run_if(in_state(GameState::Playing)),
                 ),
             );
     }
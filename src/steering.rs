@@ -0,0 +1,120 @@
+use crate::{
+    ai::{Brain, update_brains},
+    constant::*,
+    debris::Debris,
+    enemy::Enemy,
+    movement::{Movement, apply_movement_patterns},
+    player::Player,
+    state::GameState,
+};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+// Weights of each steering behaviour, blended on top of the ship's own flight
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Steering {
+    // Keep clear of other enemies
+    pub separation: f32,
+    // Swerve around debris
+    pub avoidance: f32,
+    // Drift towards the player
+    pub seek: f32,
+    // Back off when the player comes within `flee_radius`
+    pub flee: f32,
+    pub flee_radius: f32,
+}
+
+impl Default for Steering {
+    fn default() -> Self {
+        Steering {
+            separation: 1.0,
+            avoidance: 1.0,
+            seek: 0.0,
+            flee: 0.0,
+            flee_radius: 0.0,
+        }
+    }
+}
+
+// Push away from `other`, strongest when touching and fading out at `radius`
+fn repel(position: Vec2, other: Vec2, radius: f32) -> Vec2 {
+    let away = position - other;
+    let distance = away.length();
+    if distance >= radius {
+        return Vec2::ZERO;
+    }
+    // Stacked exactly on top of each other, pick a side
+    let direction = if distance > 0.0 {
+        away / distance
+    } else {
+        Vec2::X
+    };
+    direction * (1.0 - distance / radius)
+}
+
+// Only free flying ships steer, path followers and formation slots are choreographed.
+// Kamikazes are left out too, their velocity carries over between frames so the push
+// would keep adding up
+#[allow(clippy::type_complexity)]
+pub fn apply_steering(
+    mut steering_query: Query<
+        (Entity, &Steering, &Transform, &mut Velocity),
+        Or<(With<Movement>, With<Brain>)>,
+    >,
+    neighbour_query: Query<(Entity, &Transform), With<Enemy>>,
+    debris_query: Query<(&Transform, &Debris), Without<Enemy>>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let player_position = player_query
+        .single()
+        .ok()
+        .map(|transform| transform.translation.truncate());
+
+    for (entity, steering, transform, mut velocity) in steering_query.iter_mut() {
+        let position = transform.translation.truncate();
+        let mut force = Vec2::ZERO;
+
+        if steering.separation > 0.0 {
+            for (other, other_transform) in neighbour_query.iter() {
+                if other != entity {
+                    force += steering.separation
+                        * repel(
+                            position,
+                            other_transform.translation.truncate(),
+                            STEERING_SEPARATION_RADIUS,
+                        );
+                }
+            }
+        }
+
+        if steering.avoidance > 0.0 {
+            for (rock, debris) in debris_query.iter() {
+                let radius = debris.size.radius() + STEERING_AVOID_MARGIN;
+                force += steering.avoidance * repel(position, rock.translation.truncate(), radius);
+            }
+        }
+
+        if let Some(target) = player_position {
+            force += steering.seek * (target - position).normalize_or_zero();
+            if steering.flee > 0.0 {
+                force += steering.flee * repel(position, target, steering.flee_radius);
+            }
+        }
+
+        velocity.linvel += force.clamp_length_max(1.0) * STEERING_MAX_SPEED;
+    }
+}
+
+pub struct SteeringPlugin;
+
+impl Plugin for SteeringPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            apply_steering
+                .after(apply_movement_patterns)
+                .after(update_brains)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
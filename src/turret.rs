@@ -0,0 +1,259 @@
+use crate::{
+    constant::*,
+    enemy::{Enemy, enemy_weapon},
+    health::{DamageEvent, Health, HitFlash},
+    player::{Player, PlayerMotion},
+    state::GameState,
+    weapon::{AimMode, SpreadPattern, Weapon, lead_direction},
+};
+use bevy::{prelude::*, sprite::Anchor};
+use bevy_rapier2d::prelude::*;
+use serde::Deserialize;
+
+// Barrel length of each `PNG/Parts/gunNN.png` sprite
+const GUN_LENGTHS: [f32; 11] = [
+    36.0, 33.0, 36.0, 41.0, 41.0, 41.0, 38.0, 41.0, 47.0, 52.0, 52.0,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+pub enum TurretSize {
+    #[default]
+    Small,
+    Big,
+}
+
+impl TurretSize {
+    pub fn sprite(&self) -> &'static str {
+        match self {
+            TurretSize::Small => "PNG/Parts/turretBase_small.png",
+            TurretSize::Big => "PNG/Parts/turretBase_big.png",
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        match self {
+            TurretSize::Small => Vec2::new(26.0, 26.0),
+            TurretSize::Big => Vec2::new(41.0, 41.0),
+        }
+    }
+
+    pub fn health(&self) -> u16 {
+        match self {
+            TurretSize::Small => 3,
+            TurretSize::Big => 8,
+        }
+    }
+
+    pub fn score(&self) -> u32 {
+        match self {
+            TurretSize::Small => 15,
+            TurretSize::Big => 40,
+        }
+    }
+}
+
+fn default_turret_interval() -> f32 {
+    2.0
+}
+
+// A turret as written in level data, either on the terrain or mounted on a group's ships
+#[derive(Debug, Clone, Deserialize)]
+pub struct TurretDef {
+    #[serde(default)]
+    pub size: TurretSize,
+    // Which `gunNN.png` barrel to use, 0 to 10
+    #[serde(default)]
+    pub gun: usize,
+    #[serde(default = "default_turret_interval")]
+    pub fire_interval: f32,
+    // How far shots lead a moving player, 0 to 1
+    #[serde(default)]
+    pub lead: f32,
+    // Relative to the carrier, or the x position a terrain turret scrolls in at
+    #[serde(default)]
+    pub offset: (f32, f32),
+    // Seconds after the wave starts, terrain turrets only
+    #[serde(default)]
+    pub delay: f32,
+}
+
+#[derive(Component)]
+pub struct Turret {
+    pub gun: Entity,
+    // Current barrel heading in radians, 0 pointing straight down
+    pub angle: f32,
+    pub lead: f32,
+    pub score: u32,
+}
+
+// Rotating barrel, a child of the turret base
+#[derive(Component)]
+pub struct TurretGun;
+
+// Turret riding on another ship, destroyed along with it
+#[derive(Component)]
+pub struct TurretMount {
+    pub carrier: Entity,
+    pub offset: Vec2,
+}
+
+// Spawn a turret base with its gun, callers give it terrain scrolling or a `TurretMount`
+pub fn spawn_turret(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    def: &TurretDef,
+    position: Vec2,
+) -> Entity {
+    let gun_index = def.gun.min(GUN_LENGTHS.len() - 1);
+    let gun_length = GUN_LENGTHS[gun_index];
+    let gun = commands
+        .spawn((
+            // Pivot near the back of the barrel, over the centre of the base
+            Transform::from_xyz(0.0, 0.0, 0.1)
+                .with_rotation(Quat::from_rotation_z(std::f32::consts::PI)),
+            Sprite {
+                image: asset_server.load(format!("PNG/Parts/gun{:02}.png", gun_index)),
+                anchor: Anchor::Custom(Vec2::new(0.0, -0.3)),
+                ..default()
+            },
+            TurretGun,
+        ))
+        .id();
+
+    let mut weapon = enemy_weapon(def.fire_interval, SpreadPattern::SINGLE, 0.0);
+    weapon.aim = AimMode::Fixed(Vec2::NEG_Y);
+    weapon.muzzle = Vec2::new(0.0, -gun_length * 0.7);
+    weapon.firing = false;
+
+    let size = def.size.size();
+    commands
+        .spawn((
+            RigidBody::Dynamic,
+            GravityScale(0.0),
+            Velocity {
+                linvel: Vec2::new(0.0, -TERRAIN_SCROLL_SPEED),
+                angvel: 0.0,
+            },
+            ActiveEvents::COLLISION_EVENTS,
+            Sensor,
+            Collider::ball(size.x / 2.0),
+            Transform::from_translation(position.extend(0.2)),
+            Sprite {
+                image: asset_server.load(def.size.sprite()),
+                custom_size: Some(size),
+                ..default()
+            },
+            Enemy,
+            Turret {
+                gun,
+                angle: 0.0,
+                lead: def.lead,
+                score: def.size.score(),
+            },
+            Health::new(def.size.health()),
+            weapon,
+        ))
+        .add_child(gun)
+        .id()
+}
+
+// Swing each barrel towards the player and only fire once it is lined up
+#[allow(clippy::type_complexity)]
+pub fn aim_turrets(
+    time: Res<Time>,
+    mut turret_query: Query<(&mut Turret, &Transform, &mut Weapon), Without<TurretGun>>,
+    mut gun_query: Query<&mut Transform, With<TurretGun>>,
+    player_query: Query<(&Transform, &PlayerMotion), (With<Player>, Without<TurretGun>)>,
+) {
+    let player = player_query
+        .single()
+        .ok()
+        .map(|(transform, motion)| (transform.translation.truncate(), motion.0));
+
+    for (mut turret, transform, mut weapon) in turret_query.iter_mut() {
+        let Some((target, target_velocity)) = player else {
+            weapon.firing = false;
+            continue;
+        };
+        let origin = transform.translation.truncate();
+        let desired = lead_direction(origin, target, target_velocity, weapon.speed, turret.lead);
+        if desired == Vec2::ZERO {
+            continue;
+        }
+
+        let current = Vec2::from_angle(turret.angle).rotate(Vec2::NEG_Y);
+        let max_turn = TURRET_TURN_RATE * time.delta_secs();
+        let error = current.angle_to(desired);
+        turret.angle += error.clamp(-max_turn, max_turn);
+
+        let heading = Vec2::from_angle(turret.angle).rotate(Vec2::NEG_Y);
+        weapon.aim = AimMode::Fixed(heading);
+        weapon.muzzle = heading * weapon.muzzle.length();
+        weapon.firing = error.abs() < TURRET_FIRE_ARC;
+
+        if let Ok(mut gun) = gun_query.get_mut(turret.gun) {
+            gun.rotation = Quat::from_rotation_z(Vec2::Y.angle_to(heading));
+        }
+    }
+}
+
+// Keep mounted turrets on their carrier, like boss parts
+pub fn follow_carriers(
+    mut commands: Commands,
+    time: Res<Time>,
+    carrier_query: Query<&Transform, Without<TurretMount>>,
+    mut turret_query: Query<(Entity, &TurretMount, &Transform, &mut Velocity)>,
+) {
+    let delta = time.delta_secs();
+    if delta <= 0.0 {
+        return;
+    }
+
+    for (entity, mount, transform, mut velocity) in turret_query.iter_mut() {
+        let Ok(carrier) = carrier_query.get(mount.carrier) else {
+            // Carrier is gone, the turret goes down with it
+            commands.entity(entity).try_despawn();
+            continue;
+        };
+        let target = carrier.translation.truncate() + mount.offset;
+        velocity.linvel = (target - transform.translation.truncate()) / delta;
+    }
+}
+
+// Turret counterpart to `apply_enemy_damage`, the gun is despawned with its base
+pub fn apply_turret_damage(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut damage_events: EventReader<DamageEvent>,
+    mut turret_query: Query<(&mut Health, &Turret)>,
+    mut game_values: ResMut<GameValues>,
+) {
+    for event in damage_events.read() {
+        let Ok((mut health, turret)) = turret_query.get_mut(event.target) else {
+            continue;
+        };
+        if health.current == 0 {
+            continue;
+        }
+
+        if health.take(event.amount) {
+            game_values.score += turret.score;
+            commands.entity(event.target).try_despawn();
+            commands.spawn(AudioPlayer::new(asset_server.load("Bonus/sfx_zap.ogg")));
+        } else {
+            commands.entity(event.target).insert(HitFlash::default());
+        }
+    }
+}
+
+pub struct TurretPlugin;
+
+impl Plugin for TurretPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (aim_turrets, follow_carriers, apply_turret_damage)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
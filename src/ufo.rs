@@ -0,0 +1,236 @@
+use crate::{
+    constant::*,
+    enemy::Enemy,
+    health::{DamageEvent, Health, HitFlash},
+    outline::OutlineCollider,
+    pickup::{PickupKind, spawn_pickup},
+    state::GameState,
+};
+use bevy::{
+    audio::{AddAudioSource, Source},
+    prelude::*,
+};
+use bevy_rapier2d::prelude::*;
+use rand::{Rng, seq::IndexedRandom};
+use std::{f32::consts::TAU, time::Duration};
+
+const UFO_SPRITES: [&str; 4] = [
+    "PNG/Players/ufoRed.png",
+    "PNG/Players/ufoBlue.png",
+    "PNG/Players/ufoGreen.png",
+    "PNG/Players/ufoYellow.png",
+];
+
+// Counts down to the next saucer, re-rolled each time one appears
+#[derive(Resource)]
+pub struct UfoTimer(pub Timer);
+
+impl UfoTimer {
+    fn random() -> Self {
+        let seconds = rand::rng().random_range(UFO_INTERVAL.0..=UFO_INTERVAL.1);
+        UfoTimer(Timer::from_seconds(seconds, TimerMode::Once))
+    }
+}
+
+const HUM_SAMPLE_RATE: u32 = 44_100;
+
+// Warbling drone played while a saucer is on screen, synthesised so it shares a sound
+// with nothing else in the game
+#[derive(Asset, TypePath)]
+pub struct UfoHum;
+
+// Endless sine wave whose pitch swings up and down around `UFO_HUM_PITCH`
+pub struct UfoHumDecoder {
+    // Position through the current wave and the warble, both as fractions of a cycle
+    phase: f32,
+    warble: f32,
+}
+
+impl Iterator for UfoHumDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let (swing, rate) = UFO_HUM_WARBLE;
+        let pitch = UFO_HUM_PITCH + swing * (TAU * self.warble).sin();
+        self.phase = (self.phase + pitch / HUM_SAMPLE_RATE as f32).fract();
+        self.warble = (self.warble + rate / HUM_SAMPLE_RATE as f32).fract();
+        // Kept quiet, it plays under everything else
+        Some((TAU * self.phase).sin() * 0.25)
+    }
+}
+
+impl Source for UfoHumDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        HUM_SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+impl Decodable for UfoHum {
+    type DecoderItem = f32;
+    type Decoder = UfoHumDecoder;
+
+    fn decoder(&self) -> UfoHumDecoder {
+        UfoHumDecoder {
+            phase: 0.0,
+            warble: 0.0,
+        }
+    }
+}
+
+// Shared handle to the hum, so every saucer plays the same asset
+#[derive(Resource)]
+pub struct UfoHumHandle(pub Handle<UfoHum>);
+
+fn add_ufo_hum(mut commands: Commands, mut hums: ResMut<Assets<UfoHum>>) {
+    commands.insert_resource(UfoHumHandle(hums.add(UfoHum)));
+}
+
+// Mystery saucer crossing the top of the screen, worth a bonus and a pickup
+#[derive(Component)]
+pub struct BonusUfo;
+
+// Floating score left where the saucer was shot down
+#[derive(Component)]
+pub struct UfoScoreText(pub Timer);
+
+pub fn spawn_ufo(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    hum: Res<UfoHumHandle>,
+    mut ufo_timer: ResMut<UfoTimer>,
+    ufo_query: Query<(), With<BonusUfo>>,
+) {
+    // Only one on screen at a time
+    if !ufo_query.is_empty() || !ufo_timer.0.tick(time.delta()).finished() {
+        return;
+    }
+    *ufo_timer = UfoTimer::random();
+
+    let mut rng = rand::rng();
+    let side = if rng.random_bool(0.5) { 1.0 } else { -1.0 };
+    let sprite = *UFO_SPRITES.choose(&mut rng).unwrap();
+    commands
+        .spawn((
+            RigidBody::Dynamic,
+            GravityScale(0.0),
+            Velocity {
+                linvel: Vec2::new(-side * UFO_SPEED, 0.0),
+                angvel: 2.0,
+            },
+            ActiveEvents::COLLISION_EVENTS,
+            Sensor,
+            Collider::ball(UFO_SIZE.x / 2.0),
+            OutlineCollider,
+            Transform::from_xyz(
+                side * (WINDOW_WIDTH / 2.0 + UFO_SIZE.x),
+                WINDOW_HEIGHT / 2.0 - UFO_SIZE.y * 1.5,
+                0.0,
+            ),
+            Sprite {
+                image: asset_server.load(sprite),
+                custom_size: Some(UFO_SIZE),
+                ..default()
+            },
+            Enemy,
+            BonusUfo,
+            Health::new(UFO_HEALTH),
+        ))
+        // Hums for as long as it is on screen, the sound never ends by itself
+        .with_child(AudioPlayer(hum.0.clone()));
+}
+
+// Saucer counterpart to `apply_enemy_damage`, `cleanup_enemy` removes it past the far edge
+pub fn apply_ufo_damage(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut damage_events: EventReader<DamageEvent>,
+    mut ufo_query: Query<(&mut Health, &Transform), With<BonusUfo>>,
+    mut game_values: ResMut<GameValues>,
+) {
+    let mut rng = rand::rng();
+    for event in damage_events.read() {
+        let Ok((mut health, transform)) = ufo_query.get_mut(event.target) else {
+            continue;
+        };
+        if health.current == 0 {
+            continue;
+        }
+
+        if !health.take(event.amount) {
+            commands.entity(event.target).insert(HitFlash::default());
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let bonus = *UFO_SCORES.choose(&mut rng).unwrap();
+        game_values.score += bonus;
+
+        // Always pays out a pickup
+        let kind = match rng.random_range(0..3) {
+            0 => PickupKind::WeaponUpgrade,
+            1 => PickupKind::MissileAmmo,
+            _ => PickupKind::Bomb,
+        };
+        spawn_pickup(&mut commands, &asset_server, kind, position);
+
+        commands.entity(event.target).try_despawn();
+        commands.spawn(AudioPlayer::new(
+            asset_server.load("Bonus/sfx_shieldUp.ogg"),
+        ));
+        commands.spawn((
+            Text2d::new(bonus.to_string()),
+            TextFont {
+                font: asset_server.load("Bonus/kenvector_future.ttf"),
+                font_size: 24.0,
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.85, 0.2)),
+            Transform::from_translation(position.extend(1.0)),
+            UfoScoreText(Timer::from_seconds(1.5, TimerMode::Once)),
+        ));
+    }
+}
+
+pub fn update_ufo_score_text(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut text_query: Query<(Entity, &mut UfoScoreText, &mut Transform, &mut TextColor)>,
+) {
+    for (entity, mut text, mut transform, mut color) in text_query.iter_mut() {
+        if text.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        } else {
+            // Drift up while fading
+            transform.translation.y += 30.0 * time.delta_secs();
+            color.0.set_alpha(text.0.fraction_remaining());
+        }
+    }
+}
+
+pub struct UfoPlugin;
+
+impl Plugin for UfoPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_source::<UfoHum>()
+            .insert_resource(UfoTimer::random())
+            .add_systems(Startup, add_ufo_hum)
+            .add_systems(
+                Update,
+                (spawn_ufo, apply_ufo_damage, update_ufo_score_text)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
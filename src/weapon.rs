@@ -0,0 +1,243 @@
+use crate::{
+    constant::*,
+    enemy::EnemyBullet,
+    player::{Player, PlayerBullet, PlayerMotion},
+    state::GameState,
+};
+use bevy::{prelude::*, sprite::Anchor};
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+// How a weapon picks the direction of its shots
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AimMode {
+    // Always fire along this direction
+    Fixed(Vec2),
+    // Fire at the player, with a random velocity offset of up to `inaccuracy`
+    AtTarget { inaccuracy: f32 },
+    // Fire where the player will be, `accuracy` from 0 (current position) to 1 (exact intercept)
+    Lead { accuracy: f32, inaccuracy: f32 },
+}
+
+// Direction that intercepts a target moving at constant velocity, partly led by `accuracy`
+pub fn lead_direction(
+    origin: Vec2,
+    target: Vec2,
+    target_velocity: Vec2,
+    speed: f32,
+    accuracy: f32,
+) -> Vec2 {
+    // Solve |offset + velocity * t| = speed * t for the earliest positive t
+    let offset = target - origin;
+    let a = target_velocity.length_squared() - speed * speed;
+    let b = 2.0 * offset.dot(target_velocity);
+    let c = offset.length_squared();
+
+    let time = if a.abs() < f32::EPSILON {
+        // Bullet and target equally fast, the equation is linear
+        if b < 0.0 { -c / b } else { -1.0 }
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            -1.0
+        } else {
+            let root = discriminant.sqrt();
+            let (t1, t2) = ((-b - root) / (2.0 * a), (-b + root) / (2.0 * a));
+            match (t1 > 0.0, t2 > 0.0) {
+                (true, true) => t1.min(t2),
+                (true, false) => t1,
+                (false, true) => t2,
+                (false, false) => -1.0,
+            }
+        }
+    };
+
+    // No intercept when the target outruns the bullet, aim straight at it instead
+    let time = if time > 0.0 {
+        time * accuracy.clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (target + target_velocity * time - origin).normalize_or_zero()
+}
+
+// Fan of projectiles fired in a single shot
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpreadPattern {
+    pub count: u8,
+    // Angle in degrees between neighbouring projectiles
+    pub angle: f32,
+    // Horizontal distance between neighbouring muzzles
+    pub spacing: f32,
+}
+
+impl SpreadPattern {
+    pub const SINGLE: SpreadPattern = SpreadPattern {
+        count: 1,
+        angle: 0.0,
+        spacing: 0.0,
+    };
+
+    // (muzzle offset, rotation in radians) for every projectile, centred on the aim direction
+    pub fn shots(&self) -> impl Iterator<Item = (f32, f32)> + '_ {
+        let middle = (self.count as f32 - 1.0) / 2.0;
+        (0..self.count).map(move |i| {
+            let step = i as f32 - middle;
+            // Positive offsets fan out clockwise so outer shots lean away from the centre
+            (step * self.spacing, -(step * self.angle).to_radians())
+        })
+    }
+}
+
+#[derive(Component, Clone)]
+pub struct Weapon {
+    pub cooldown: Timer,
+    pub projectile: &'static str,
+    pub projectile_size: Vec2,
+    pub speed: f32,
+    pub damage: u8,
+    pub spread: SpreadPattern,
+    pub aim: AimMode,
+    pub sound: Option<&'static str>,
+    // Where projectiles leave the shooter, relative to its centre
+    pub muzzle: Vec2,
+    // Enemies always hold the trigger, the player's follows input
+    pub firing: bool,
+}
+
+// Damage a projectile deals on hit
+#[derive(Component)]
+pub struct Damage(pub u8);
+
+// Enemy projectiles are dynamic bodies so they register hits on the kinematic player,
+// and sensors so they don't bounce off rocks
+pub fn spawn_enemy_bullet(
+    commands: &mut Commands,
+    collider: Collider,
+    transform: Transform,
+    velocity: Velocity,
+    sprite: Sprite,
+    damage: u8,
+) -> Entity {
+    commands
+        .spawn((
+            RigidBody::Dynamic,
+            collider,
+            velocity,
+            transform,
+            sprite,
+            GravityScale(0.0),
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+            EnemyBullet,
+            Bullet,
+            Damage(damage),
+        ))
+        .id()
+}
+
+pub fn fire_weapons(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    mut shooter_query: Query<(&Transform, &mut Weapon, Has<Player>)>,
+    player_query: Query<(&Transform, &PlayerMotion)>,
+) {
+    let mut rng = rand::rng();
+    let player = player_query
+        .single()
+        .ok()
+        .map(|(transform, motion)| (transform.translation.truncate(), motion.0));
+
+    for (transform, mut weapon, is_player) in shooter_query.iter_mut() {
+        if !weapon.cooldown.tick(time.delta()).just_finished() || !weapon.firing {
+            continue;
+        }
+
+        let origin = transform.translation.truncate() + weapon.muzzle;
+        let direction = match weapon.aim {
+            AimMode::Fixed(direction) => direction.normalize_or_zero(),
+            AimMode::AtTarget { .. } => {
+                // Nothing to aim at without a player
+                let Some((target, _)) = player else {
+                    continue;
+                };
+                (target - origin).normalize_or_zero()
+            }
+            AimMode::Lead { accuracy, .. } => {
+                let Some((target, target_velocity)) = player else {
+                    continue;
+                };
+                lead_direction(origin, target, target_velocity, weapon.speed, accuracy)
+            }
+        };
+        if direction == Vec2::ZERO {
+            continue;
+        }
+
+        for (offset, rotation) in weapon.spread.shots() {
+            let mut velocity = Vec2::from_angle(rotation).rotate(direction) * weapon.speed;
+            if let AimMode::AtTarget { inaccuracy } | AimMode::Lead { inaccuracy, .. } = weapon.aim
+            {
+                velocity += Vec2::new(
+                    rng.random_range(-inaccuracy..=inaccuracy),
+                    rng.random_range(-inaccuracy..=inaccuracy),
+                );
+            }
+
+            let position = origin + direction.perp() * -offset;
+            let transform = Transform::from_translation(position.extend(0.0))
+                .with_rotation(Quat::from_rotation_z(Vec2::Y.angle_to(velocity)));
+            let sprite = Sprite {
+                image: asset_server.load(weapon.projectile),
+                custom_size: Some(weapon.projectile_size),
+                anchor: Anchor::Center,
+                ..default()
+            };
+            let collider = Collider::cuboid(
+                weapon.projectile_size.x / 2.0,
+                weapon.projectile_size.y / 2.0,
+            );
+            let velocity = Velocity {
+                linvel: velocity,
+                angvel: 0.0,
+            };
+
+            if is_player {
+                commands.spawn((
+                    RigidBody::KinematicVelocityBased,
+                    collider,
+                    transform,
+                    velocity,
+                    sprite,
+                    Sensor,
+                    Bullet,
+                    PlayerBullet,
+                    Damage(weapon.damage),
+                    ActiveEvents::COLLISION_EVENTS,
+                ));
+            } else {
+                spawn_enemy_bullet(
+                    &mut commands,
+                    collider,
+                    transform,
+                    velocity,
+                    sprite,
+                    weapon.damage,
+                );
+            }
+        }
+
+        if let Some(sound) = weapon.sound {
+            commands.spawn(AudioPlayer::new(asset_server.load(sound)));
+        }
+    }
+}
+
+pub struct WeaponPlugin;
+
+impl Plugin for WeaponPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, fire_weapons.run_if(in_state(GameState::Playing)));
+    }
+}